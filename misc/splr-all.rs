@@ -7,7 +7,6 @@ use std::{env::args, path::Path};
 ///```ignore
 /// cargo run --features incremental_solver --example all-solutions -- a.cnf
 ///```
-
 fn main() {
     let cnf_file: String = args().nth(1).expect("takes an arg");
     run(Path::new(&cnf_file));
@@ -169,6 +169,7 @@ trait VarOrderIF {
     fn get_root(&mut self) -> VarId;
     fn len(&self) -> usize;
     fn insert(&mut self, vi: VarId) -> usize;
+    #[cfg(feature = "trail_saving")]
     fn is_empty(&self) -> bool;
     fn remove(&mut self, vi: VarId) -> Option<usize>;
 }
@@ -211,6 +212,7 @@ impl VarOrderIF for VarIdHeap {
         self.idxs[0] = n;
         n as usize
     }
+    #[cfg(feature = "trail_saving")]
     fn is_empty(&self) -> bool {
         self.idxs[0] == 0
     }
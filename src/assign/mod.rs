@@ -1,5 +1,5 @@
-/// Module `assign` implements Boolean Constraint Propagation and decision var selection.
-/// This version can handle Chronological and Non Chronological Backtrack.
+//! Module `assign` implements Boolean Constraint Propagation and decision var selection.
+//! This version can handle Chronological and Non Chronological Backtrack.
 
 /// Ema
 mod ema;
@@ -9,7 +9,10 @@ mod heap;
 mod propagate;
 /// Var rewarding
 #[cfg_attr(feature = "EVSIDS", path = "evsids.rs")]
-#[cfg_attr(feature = "LRB_rewarding", path = "learning_rate.rs")]
+#[cfg_attr(
+    any(feature = "LRB_rewarding", not(feature = "EVSIDS")),
+    path = "learning_rate.rs"
+)]
 mod reward;
 /// Decision var selection
 mod select;
@@ -36,6 +39,9 @@ pub use self::trail_saving::TrailSavingIF;
 #[cfg(any(feature = "best_phases_tracking", feature = "rephase"))]
 use std::collections::HashMap;
 
+#[cfg(feature = "rephase")]
+use crate::config::RephasePolicy;
+
 /// API about assignment like
 /// [`decision_level`](`crate::assign::AssignIF::decision_level`),
 /// [`stack`](`crate::assign::AssignIF::stack`),
@@ -164,6 +170,10 @@ pub struct AssignStack {
     best_phases: HashMap<VarId, (bool, AssignReason)>,
     #[cfg(feature = "rephase")]
     phase_age: usize,
+    #[cfg(feature = "rephase")]
+    rephase_policy: RephasePolicy,
+    #[cfg(feature = "rephase")]
+    rephase_seed: u64,
 
     //
     //## Stage
@@ -185,6 +195,10 @@ pub struct AssignStack {
     pub num_eliminated_vars: usize,
     num_decision: usize,
     num_propagation: usize,
+    /// the number of implications by binary links in `propagate`
+    num_bi_propagation: usize,
+    /// the number of implications by longer clauses in `propagate`
+    num_long_propagation: usize,
     pub num_conflict: usize,
     num_restart: usize,
     /// Assign rate EMA
@@ -293,6 +307,10 @@ pub mod property {
         NumConflict,
         NumDecision,
         NumPropagation,
+        /// the number of implications by binary links
+        NumBiPropagation,
+        /// the number of implications by clauses longer than two
+        NumLongPropagation,
         NumRephase,
         NumRestart,
         //
@@ -310,10 +328,12 @@ pub mod property {
         RootLevel,
     }
 
-    pub const USIZES: [Tusize; 14] = [
+    pub const USIZES: [Tusize; 16] = [
         Tusize::NumConflict,
         Tusize::NumDecision,
         Tusize::NumPropagation,
+        Tusize::NumBiPropagation,
+        Tusize::NumLongPropagation,
         Tusize::NumRephase,
         Tusize::NumRestart,
         Tusize::NumVar,
@@ -334,6 +354,8 @@ pub mod property {
                 Tusize::NumConflict => self.num_conflict,
                 Tusize::NumDecision => self.num_decision,
                 Tusize::NumPropagation => self.num_propagation,
+                Tusize::NumBiPropagation => self.num_bi_propagation,
+                Tusize::NumLongPropagation => self.num_long_propagation,
                 Tusize::NumRephase => self.num_rephase,
                 Tusize::NumRestart => self.num_restart,
                 Tusize::NumVar => self.num_vars,
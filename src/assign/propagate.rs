@@ -421,6 +421,7 @@ impl PropagateIF for AssignStack {
                     }
                     None => {
                         debug_assert!(cdb[cid].lit0() == false_lit || cdb[cid].lit1() == false_lit);
+                        self.num_bi_propagation += 1;
                         self.assign_by_implication(
                             blocker,
                             minimized_reason!(propagating),
@@ -541,6 +542,7 @@ impl PropagateIF for AssignStack {
                 debug_assert_eq!(cdb[cid].lit0(), cached);
                 debug_assert_eq!(self.assigned(cached), None);
                 debug_assert!(other_watch_value.is_none());
+                self.num_long_propagation += 1;
                 self.assign_by_implication(
                     cached,
                     AssignReason::Implication(cid),
@@ -765,7 +767,6 @@ impl AssignStack {
         assert_ne!(self.assigned(b0), Some(false));
         assert_ne!(self.assigned(b1), Some(false));
     }
-    ///
     fn propagate_at_root_level(&mut self, cdb: &mut impl ClauseDBIF) -> MaybeInconsistent {
         let mut num_propagated = 0;
         while num_propagated < self.trail.len() {
@@ -1,7 +1,7 @@
-/// Decision var selection
+//! Decision var selection
 
 #[cfg(feature = "rephase")]
-use super::property;
+use {super::property, crate::config::RephasePolicy};
 
 use {
     super::{AssignStack, VarHeapIF},
@@ -35,12 +35,17 @@ pub trait VarSelectIF {
     fn override_rephasing_target(&mut self, assignment: &HashMap<VarId, bool>) -> usize;
     /// give rewards to vars selected by SLS
     fn reward_by_sls(&mut self, assignment: &HashMap<VarId, bool>) -> usize;
+    #[cfg(feature = "best_phases_tracking")]
+    /// give rewards to vars in the unreachable core, which are unassigned in the best phases
+    fn reward_core_vars(&mut self) -> usize;
     #[cfg(feature = "rephase")]
     /// select rephasing target
     fn select_rephasing_target(&mut self);
     #[cfg(feature = "rephase")]
     /// check the consistency
     fn check_consistency_of_best_phases(&mut self);
+    /// invert the saved phases and forget the best phases, to leave a stagnated search.
+    fn reset_phases(&mut self);
     /// select a new decision variable.
     fn select_decision_literal(&mut self) -> Lit;
     /// update the internal heap on var order.
@@ -81,6 +86,24 @@ impl VarSelectIF for AssignStack {
         }
         num_flipped
     }
+    #[cfg(feature = "best_phases_tracking")]
+    fn reward_core_vars(&mut self) -> usize {
+        let mut num_core = 0;
+        for vi in 1..self.var.len() {
+            if (var_assign!(self, vi).is_some() && self.level[vi] == self.root_level)
+                || self.var[vi].is(FlagVar::ELIMINATED)
+                || self.best_phases.contains_key(&vi)
+            {
+                continue;
+            }
+            num_core += 1;
+            let v = &mut self.var[vi];
+            v.reward *= self.activity_decay;
+            v.reward += self.activity_anti_decay;
+            self.update_heap(vi);
+        }
+        num_core
+    }
     fn reward_by_sls(&mut self, assignment: &HashMap<VarId, bool>) -> usize {
         let mut num_flipped = 0;
         for (vi, b) in assignment.iter() {
@@ -97,6 +120,29 @@ impl VarSelectIF for AssignStack {
     }
     #[cfg(feature = "rephase")]
     fn select_rephasing_target(&mut self) {
+        match self.rephase_policy {
+            RephasePolicy::BestPhase => (),
+            RephasePolicy::LastPhase => return,
+            RephasePolicy::Random => {
+                self.num_rephase += 1;
+                for v in self.var.iter_mut().skip(1) {
+                    // xorshift64
+                    self.rephase_seed ^= self.rephase_seed << 13;
+                    self.rephase_seed ^= self.rephase_seed >> 7;
+                    self.rephase_seed ^= self.rephase_seed << 17;
+                    v.set(FlagVar::PHASE, self.rephase_seed & 1 == 1);
+                }
+                return;
+            }
+            RephasePolicy::Inverted => {
+                self.num_rephase += 1;
+                for v in self.var.iter_mut().skip(1) {
+                    let phase = v.is(FlagVar::PHASE);
+                    v.set(FlagVar::PHASE, !phase);
+                }
+                return;
+            }
+        }
         if self.best_phases.is_empty() {
             return;
         }
@@ -126,6 +172,15 @@ impl VarSelectIF for AssignStack {
             self.num_best_assign = self.num_asserted_vars + self.num_eliminated_vars;
         }
     }
+    fn reset_phases(&mut self) {
+        for v in self.var.iter_mut().skip(1) {
+            let phase = v.is(FlagVar::PHASE);
+            v.set(FlagVar::PHASE, !phase);
+        }
+        #[cfg(feature = "best_phases_tracking")]
+        self.best_phases.clear();
+        self.num_best_assign = self.num_asserted_vars + self.num_eliminated_vars;
+    }
     fn select_decision_literal(&mut self) -> Lit {
         let vi = self.select_var();
         Lit::from((vi, self.var[vi].is(FlagVar::PHASE)))
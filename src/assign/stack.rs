@@ -11,6 +11,9 @@ use {
 #[cfg(any(feature = "best_phases_tracking", feature = "rephase"))]
 use std::collections::HashMap;
 
+#[cfg(feature = "rephase")]
+use crate::config::RephasePolicy;
+
 #[cfg(feature = "trail_saving")]
 use super::TrailSavingIF;
 
@@ -43,6 +46,10 @@ impl Default for AssignStack {
             best_phases: HashMap::new(),
             #[cfg(feature = "rephase")]
             phase_age: 0,
+            #[cfg(feature = "rephase")]
+            rephase_policy: RephasePolicy::BestPhase,
+            #[cfg(feature = "rephase")]
+            rephase_seed: 0x2545_f491_4f6c_dd1d,
 
             stage_scale: 1,
             eliminated: Vec::new(),
@@ -52,6 +59,8 @@ impl Default for AssignStack {
             num_eliminated_vars: 0,
             num_decision: 0,
             num_propagation: 0,
+            num_bi_propagation: 0,
+            num_long_propagation: 0,
             num_conflict: 0,
             num_restart: 0,
             assign_rate: ProgressASG::default(),
@@ -120,6 +129,9 @@ impl Instantiate for AssignStack {
             #[cfg(feature = "EVSIDS")]
             activity_decay_step: config.vrw_dcy_stp,
 
+            #[cfg(feature = "rephase")]
+            rephase_policy: config.rephase_policy,
+
             ..AssignStack::default()
         }
     }
@@ -337,6 +349,26 @@ mod tests {
     fn lit(i: i32) -> Lit {
         Lit::from(i)
     }
+    #[cfg(feature = "best_phases_tracking")]
+    #[test]
+    fn test_reward_core_vars() {
+        use crate::assign::VarSelectIF;
+        let config = Config::default();
+        let cnf = CNFDescription {
+            num_of_variables: 5,
+            ..CNFDescription::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        assert!(asg.assign_at_root_level(lit(1)).is_ok());
+        asg.best_phases.insert(2, (true, AssignReason::None));
+        asg.var[3].turn_on(FlagVar::ELIMINATED);
+        let before = asg.var.iter().map(|v| v.reward).collect::<Vec<_>>();
+        // only 4 and 5 are in the unreachable core.
+        assert_eq!(asg.reward_core_vars(), 2);
+        for (vi, r) in before.iter().enumerate().skip(1) {
+            assert_eq!(*r < asg.var[vi].reward, 4 <= vi, "var {vi}");
+        }
+    }
     #[test]
     fn test_propagation() {
         let config = Config::default();
@@ -408,4 +440,52 @@ mod tests {
         assert_eq!(asg.assigned(lit(-4)), Some(true));
         assert_eq!(asg.assigned(lit(-3)), None);
     }
+    #[test]
+    fn test_propagation_counters() {
+        use crate::cdb::{ClauseDB, ClauseDBIF};
+        let config = Config::default();
+        let cnf = CNFDescription {
+            num_of_variables: 4,
+            ..CNFDescription::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        let mut cdb = ClauseDB::instantiate(&config, &cnf);
+        cdb.new_clause(&mut asg, &mut vec![lit(-1), lit(2)], false);
+        cdb.new_clause(&mut asg, &mut vec![lit(-2), lit(-3), lit(4)], false);
+        asg.assign_by_decision(lit(1));
+        assert!(asg.propagate(&mut cdb).is_ok());
+        asg.assign_by_decision(lit(3));
+        assert!(asg.propagate(&mut cdb).is_ok());
+        assert_eq!(asg.assigned(lit(4)), Some(true));
+        assert_eq!(
+            asg.derefer(crate::assign::property::Tusize::NumBiPropagation),
+            1
+        );
+        assert_eq!(
+            asg.derefer(crate::assign::property::Tusize::NumLongPropagation),
+            1
+        );
+    }
+
+    #[test]
+    fn test_decision_counter() {
+        use crate::cdb::{ClauseDB, ClauseDBIF};
+        let config = Config::default();
+        let cnf = CNFDescription {
+            num_of_variables: 4,
+            ..CNFDescription::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        let mut cdb = ClauseDB::instantiate(&config, &cnf);
+        cdb.new_clause(&mut asg, &mut vec![lit(-1), lit(2)], false);
+        cdb.new_clause(&mut asg, &mut vec![lit(-2), lit(-3), lit(4)], false);
+        cdb.new_clause(&mut asg, &mut vec![lit(-3), lit(-4)], false);
+        asg.assign_by_decision(lit(1));
+        assert!(asg.propagate(&mut cdb).is_ok());
+        asg.assign_by_decision(lit(3));
+        assert!(asg.propagate(&mut cdb).is_err());
+        // implied literals are not counted as decisions.
+        assert_eq!(asg.derefer(crate::assign::property::Tusize::NumDecision), 2);
+        assert_eq!(asg.derefer(crate::assign::property::Tusize::NumConflict), 1);
+    }
 }
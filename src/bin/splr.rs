@@ -3,9 +3,11 @@
 use {
     splr::{
         assign, cdb,
+        cnf::run_icnf,
         config::{self, CERTIFICATION_DEFAULT_FILENAME},
         solver::*,
         state::{self, LogF64Id, LogUsizeId},
+        types::{CNFDescription, Instantiate},
         Config, EmaIF, PropertyDereference, PropertyReference, SolverError, VERSION,
     },
     std::{
@@ -24,18 +26,18 @@ const GREEN: &str = "\x1B[001m\x1B[032m";
 const BLUE: &str = "\x1B[001m\x1B[034m";
 const RESET: &str = "\x1B[000m";
 
-fn colored(v: Result<bool, &SolverError>, no_color: bool) -> Cow<'static, str> {
+fn colored(v: Option<bool>, no_color: bool) -> Cow<'static, str> {
     if no_color {
         match v {
-            Ok(false) => Cow::Borrowed("s UNSATISFIABLE"),
-            Ok(true) => Cow::Borrowed("s SATISFIABLE"),
-            Err(_) => Cow::Borrowed("s UNKNOWN"),
+            Some(false) => Cow::Borrowed("s UNSATISFIABLE"),
+            Some(true) => Cow::Borrowed("s SATISFIABLE"),
+            None => Cow::Borrowed("s UNKNOWN"),
         }
     } else {
         match v {
-            Ok(false) => Cow::from(format!("{BLUE}s UNSATISFIABLE{RESET}")),
-            Ok(true) => Cow::from(format!("{GREEN}s SATISFIABLE{RESET}")),
-            Err(_) => Cow::from(format!("{RED}s UNKNOWN{RESET}")),
+            Some(false) => Cow::from(format!("{BLUE}s UNSATISFIABLE{RESET}")),
+            Some(true) => Cow::from(format!("{GREEN}s SATISFIABLE{RESET}")),
+            None => Cow::from(format!("{RED}s UNKNOWN{RESET}")),
         }
     }
 }
@@ -72,20 +74,20 @@ fn main() {
             let no_color = config.no_color;
             thread::spawn(move || {
                 thread::sleep(Duration::from_millis(timeout * 1000));
-                println!(
-                    "{} (TimeOut): {}",
-                    colored(Err(&SolverError::TimeOut), no_color),
-                    input
-                );
+                println!("{} (TimeOut): {}", colored(None, no_color), input);
                 std::process::exit(0);
             });
         }
     }
+    if config.cnf_file.extension().map_or(false, |e| e == "icnf") {
+        run_icnf_file(&config);
+        return;
+    }
     let mut s = match Solver::build(&config) {
         Err(SolverError::EmptyClause | SolverError::RootLevelConflict(_)) => {
             println!(
                 "\x1B[1G\x1B[K{}: {}",
-                colored(Ok(false), config.no_color),
+                colored(Some(false), config.no_color),
                 config.cnf_file.file_name().unwrap().to_string_lossy(),
             );
             std::process::exit(20);
@@ -100,10 +102,28 @@ fn main() {
     std::process::exit(match res {
         Ok(Certificate::SAT(_)) => 10,
         Ok(Certificate::UNSAT) => 20,
-        Err(_) => 0,
+        Ok(Certificate::UNKNOWN) | Err(_) => 0,
     });
 }
 
+/// run an incremental CNF file and print the verdict of each query.
+fn run_icnf_file(config: &Config) {
+    let mut s = Solver::instantiate(config, &CNFDescription::default());
+    match run_icnf(&config.cnf_file, &mut s) {
+        Ok(verdicts) => {
+            for v in verdicts.iter() {
+                let v = match v {
+                    Certificate::SAT(_) => Some(true),
+                    Certificate::UNSAT => Some(false),
+                    Certificate::UNKNOWN => None,
+                };
+                println!("{}", colored(v, config.no_color));
+            }
+        }
+        Err(e) => println!("Abort: {e:?}"),
+    }
+}
+
 fn save_result<S: AsRef<str> + std::fmt::Display>(
     s: &mut Solver,
     res: &SolverResult,
@@ -141,7 +161,11 @@ fn save_result<S: AsRef<str> + std::fmt::Display>(
                 }
                 _ => (),
             }
-            println!("{}: {}", colored(Ok(true), s.state.config.no_color), input);
+            println!(
+                "{}: {}",
+                colored(Some(true), s.state.config.no_color),
+                input
+            );
             if let Err(why) = (|| {
                 buf.write_all(
                     format!("c This file was generated by splr-{VERSION} for {input}\nc \n")
@@ -175,7 +199,11 @@ fn save_result<S: AsRef<str> + std::fmt::Display>(
                     s.state.config.io_pfile.to_string_lossy()
                 );
             }
-            println!("{}: {}", colored(Ok(false), s.state.config.no_color), input);
+            println!(
+                "{}: {}",
+                colored(Some(false), s.state.config.no_color),
+                input
+            );
             if let Err(why) = (|| {
                 buf.write_all(
                     format!(
@@ -190,7 +218,11 @@ fn save_result<S: AsRef<str> + std::fmt::Display>(
                 println!("Abort: failed to save by {why}!");
             }
         }
-        Err(e) => {
+        Ok(Certificate::UNKNOWN) | Err(_) => {
+            let reason: Cow<'static, str> = match res {
+                Err(e) => Cow::from(e.to_string()),
+                _ => Cow::Borrowed("stopped by timeout or interruption"),
+            };
             match output {
                 Some(ref f) if redirect && !s.state.config.quiet_mode => println!(
                     "      Result|dump: to STDOUT instead of {} due to an IO error.",
@@ -203,8 +235,8 @@ fn save_result<S: AsRef<str> + std::fmt::Display>(
             }
             println!(
                 "{} ({}): {}",
-                colored(Err(e), s.state.config.no_color),
-                e,
+                colored(None, s.state.config.no_color),
+                reason,
                 input
             );
             if let Err(why) = (|| {
@@ -213,7 +245,7 @@ fn save_result<S: AsRef<str> + std::fmt::Display>(
                         .as_bytes(),
                 )?;
                 report(s, buf)?;
-                buf.write_all(format!("c {}\n{}\n", e, colored(Err(e), true)).as_bytes())?;
+                buf.write_all(format!("c {}\n{}\n", reason, colored(None, true)).as_bytes())?;
                 buf.write(b"0\n")
             })() {
                 println!("Abort: failed to save by {why}!");
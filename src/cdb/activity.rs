@@ -3,7 +3,6 @@ use {super::ClauseId, crate::types::*, std::num::NonZeroU32};
 
 /// clause activity
 /// Note: vivifier has its own conflict analyzer, which never call reward functions.
-
 impl ActivityIF<ClauseId> for ClauseDB {
     fn activity(&self, _cid: ClauseId) -> f64 {
         unreachable!()
@@ -16,12 +15,13 @@ impl ActivityIF<ClauseId> for ClauseDB {
         self.clause[NonZeroU32::get(cid.ordinal) as usize].update_activity(
             self.tick,
             self.activity_decay,
-            self.activity_anti_decay,
+            self.activity_anti_decay * self.activity_reward,
         );
     }
     fn update_activity_tick(&mut self) {
         self.tick += 1;
     }
+    /// Note: this resets the anti-decay rate to `1.0 - decay`.
     fn update_activity_decay(&mut self, decay: f64) {
         self.activity_decay = decay;
         self.activity_anti_decay = 1.0 - decay;
@@ -42,6 +42,19 @@ pub struct BinaryLinkDB {
     list: Vec<BinaryLinkList>,
 }
 
+impl BinaryLinkDB {
+    /// return an estimation of the number of bytes used.
+    pub fn mem_bytes(&self) -> usize {
+        self.hash.capacity() * std::mem::size_of::<((Lit, Lit), ClauseId)>()
+            + self.list.capacity() * std::mem::size_of::<BinaryLinkList>()
+            + self
+                .list
+                .iter()
+                .map(|l| l.capacity() * std::mem::size_of::<(Lit, ClauseId)>())
+                .sum::<usize>()
+    }
+}
+
 impl Instantiate for BinaryLinkDB {
     fn instantiate(_conf: &Config, cnf: &CNFDescription) -> Self {
         let num_lit = 2 * (cnf.num_of_variables + 1);
@@ -4,11 +4,12 @@ use {
         ema::ProgressLBD,
         property,
         watch_cache::*,
-        BinaryLinkDB, CertificationStore, Clause, ClauseDB, ClauseDBIF, ClauseId, ReductionType,
-        RefClause,
+        ActiveClauses, BinaryLinkDB, CertificationStore, Clause, ClauseDB, ClauseDBIF, ClauseId,
+        ClauseRef, FormulaClass, ReductionType, RefClause,
     },
     crate::{assign::AssignIF, types::*},
     std::{
+        collections::HashSet,
         num::NonZeroU32,
         ops::{Index, IndexMut, Range, RangeFrom},
         slice::{Iter, IterMut},
@@ -25,8 +26,11 @@ impl Default for ClauseDB {
             binary_link: BinaryLinkDB::default(),
             watch_cache: Vec::new(),
             freelist: Vec::new(),
+            recycle_clause_ids: false,
+            generation: Vec::new(),
             certification_store: CertificationStore::default(),
             soft_limit: 0, // 248_000_000
+            bi_clause_minimize_depth: 1,
             co_lbd_bound: 4,
             bi_clause_completion_queue: Vec::new(),
             num_bi_clause_completion: 0,
@@ -37,6 +41,8 @@ impl Default for ClauseDB {
             activity_decay: 0.99,
             #[cfg(feature = "clause_rewarding")]
             activity_anti_decay: 0.01,
+            #[cfg(feature = "clause_rewarding")]
+            activity_reward: 1.0,
 
             lbd_temp: Vec::new(),
             lbd: ProgressLBD::default(),
@@ -48,6 +54,7 @@ impl Default for ClauseDB {
             num_learnt: 0,
             num_reduction: 0,
             num_reregistration: 0,
+            num_bi_minimized_lit: 0,
             lb_entanglement: Ema2::new(1_000).with_slow(80_000).with_value(2.0),
             reduction_threshold: 0.0,
             eliminated_permanent: Vec::new(),
@@ -159,6 +166,42 @@ impl IndexMut<RangeFrom<usize>> for ClauseDB {
     }
 }
 
+/// Iterate over the literals of alive clauses, including learnt ones.
+/// The sentinel at index 0 and the recycled slots are skipped.
+///
+///```
+/// use crate::{splr::config::Config, splr::types::*};
+/// use crate::splr::solver::Solver;
+///
+/// let s = Solver::try_from((Config::default(), vec![vec![1, 2], vec![-1, 2, 3]].as_ref()))
+///     .expect("panic");
+/// let mut clauses = (&s.cdb).into_iter().map(|c| c.len()).collect::<Vec<_>>();
+/// clauses.sort();
+/// assert_eq!(clauses, vec![2, 3]);
+/// for c in &s.cdb {
+///     assert!(c.contains(&Lit::from(2)));
+/// }
+///```
+impl<'a> IntoIterator for &'a ClauseDB {
+    type Item = &'a [Lit];
+    type IntoIter = ActiveClauses<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        ActiveClauses {
+            iter: self.clause.get(1..).unwrap_or_default().iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for ActiveClauses<'a> {
+    type Item = &'a [Lit];
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .by_ref()
+            .find(|c| !c.is_dead())
+            .map(|c| c.lits.as_slice())
+    }
+}
+
 impl Instantiate for ClauseDB {
     fn instantiate(config: &Config, cnf: &CNFDescription) -> ClauseDB {
         let nv = cnf.num_of_variables;
@@ -175,12 +218,20 @@ impl Instantiate for ClauseDB {
             watch_cache: watcher,
             certification_store: CertificationStore::instantiate(config, cnf),
             soft_limit: config.c_cls_lim,
+            bi_clause_minimize_depth: config.bin_minimize_depth,
+            recycle_clause_ids: config.recycle_clause_ids,
             lbd: ProgressLBD::instantiate(config, cnf),
 
             #[cfg(feature = "clause_rewarding")]
             activity_decay: config.crw_dcy_rat,
             #[cfg(feature = "clause_rewarding")]
-            activity_anti_decay: 1.0 - config.crw_dcy_rat,
+            activity_anti_decay: if 0.0 < config.crw_anti_dcy_rat {
+                config.crw_anti_dcy_rat
+            } else {
+                1.0 - config.crw_dcy_rat
+            },
+            #[cfg(feature = "clause_rewarding")]
+            activity_reward: config.crw_rwd_inc,
 
             lbd_temp: vec![0; nv + 1],
             ..ClauseDB::default()
@@ -342,6 +393,7 @@ impl ClauseDBIF for ClauseDB {
         *num_clause += 1;
         if learnt {
             if len2 {
+                c.turn_on(FlagClause::BI_LEARNT);
                 *num_bi_learnt += 1;
             } else {
                 c.turn_on(FlagClause::LEARNT);
@@ -427,6 +479,7 @@ impl ClauseDBIF for ClauseDB {
         // assert_eq!(self.clause.iter().skip(1).filter(|c| !c.is_dead()).count(), self.num_clause);
         // if !self.clause[NonZeroU32::get(cid.ordinal) as usize].is_dead() {
         // }
+        self.bump_generation(cid);
         let c = &mut self.clause[NonZeroU32::get(cid.ordinal) as usize];
         debug_assert!(!c.is_dead());
         debug_assert!(1 < c.lits.len());
@@ -444,6 +497,7 @@ impl ClauseDBIF for ClauseDB {
     }
     fn remove_clause_sandbox(&mut self, cid: ClauseId) {
         // assert_eq!(self.clause.iter().skip(1).filter(|c| !c.is_dead()).count(), self.num_clause);
+        self.bump_generation(cid);
         let c = &mut self.clause[NonZeroU32::get(cid.ordinal) as usize];
         debug_assert!(!c.is_dead());
         debug_assert!(1 < c.lits.len());
@@ -558,62 +612,25 @@ impl ClauseDBIF for ClauseDB {
 
             // Here we assumed that there's no eliminated var in clause and *watch cache*.
             // Fortunately the current implementation purges all eliminated vars fully.
-            if p == old_l0 {
+            if p == old_l0 || p == old_l1 {
+                // Since `new_lits` keeps the order, the other watch moves to `l0`
+                // and `lits[2]` becomes `l1`. Note: the cached literal of the other
+                // watch must be updated regardless of `maintain_watch_cache`;
+                // otherwise a satisfied `p` makes propagation skip this clause.
+                debug_assert!(old_l0 == l0 || old_l1 == l0);
                 watch_cache[!p].remove_watch(&cid);
-                if old_l1 == l0 {
-                    debug_assert!(watch_cache[!l1].iter().all(|e| e.0 != cid));
-                    watch_cache[!l1].insert_watch(cid, l0);
-
-                    #[cfg(feature = "maintain_watch_cache")]
-                    {
-                        watch_cache[!l0].update_watch(cid, l1);
-                    }
-                } else if old_l1 == l1 {
-                    debug_assert!(watch_cache[!l0].iter().all(|e| e.0 != cid));
-                    watch_cache[!l0].insert_watch(cid, l1);
-
-                    #[cfg(feature = "maintain_watch_cache")]
-                    {
-                        watch_cache[!l1].update_watch(cid, l0);
-                    }
-                } else {
-                    unreachable!("transform_by_elimination");
-                }
-            } else if p == old_l1 {
-                watch_cache[!p].remove_watch(&cid);
-                if old_l0 == l0 {
-                    debug_assert!(watch_cache[!l1].iter().all(|e| e.0 != cid));
-                    watch_cache[!l1].insert_watch(cid, l0);
-
-                    #[cfg(feature = "maintain_watch_cache")]
-                    {
-                        watch_cache[!l0].update_watch(cid, l1);
-                    }
-                } else if old_l0 == l1 {
-                    debug_assert!(watch_cache[!l0].iter().all(|e| e.0 != cid));
-                    watch_cache[!l0].insert_watch(cid, l1);
-
-                    #[cfg(feature = "maintain_watch_cache")]
-                    {
-                        watch_cache[!l1].update_watch(cid, l0);
-                    }
-                } else {
-                    unreachable!("transform_by_elimination");
-                }
+                debug_assert!(watch_cache[!l1].iter().all(|e| e.0 != cid));
+                watch_cache[!l1].insert_watch(cid, l0);
+                watch_cache[!l0].update_watch(cid, l1);
             } else {
                 debug_assert_eq!(old_l0, l0);
                 debug_assert_eq!(old_l1, l1);
+                // A cached literal can be a non-watching literal like `p`.
+                watch_cache[!l0].update_watch(cid, l1);
+                watch_cache[!l1].update_watch(cid, l0);
             }
-
-            #[cfg(feature = "maintain_watch_cache")]
-            {
-                debug_assert!(watch_cache[!c.lits[0]]
-                    .iter()
-                    .any(|wc| wc.0 == cid && wc.1 == c.lits[1]));
-                debug_assert!(watch_cache[!c.lits[1]]
-                    .iter()
-                    .any(|wc| wc.0 == cid && wc.1 == c.lits[0]));
-            }
+            debug_assert!(watch_cache[!l0].iter().any(|wc| wc.0 == cid && wc.1 == l1));
+            debug_assert!(watch_cache[!l1].iter().any(|wc| wc.0 == cid && wc.1 == l0));
 
             // self.watches(cid, "after strengthen_by_elimination case:3-3");
         }
@@ -670,6 +687,9 @@ impl ClauseDBIF for ClauseDB {
                 certification_store.add_clause(new_lits);
                 certification_store.delete_clause(&c.lits);
             }
+            if c.is(FlagClause::LEARNT) {
+                c.turn_on(FlagClause::BI_LEARNT);
+            }
             c.turn_off(FlagClause::LEARNT);
             self.num_bi_clause += 1;
 
@@ -807,6 +827,7 @@ impl ClauseDBIF for ClauseDB {
                 if c.is(FlagClause::LEARNT) {
                     self.num_learnt -= 1;
                     c.turn_off(FlagClause::LEARNT);
+                    c.turn_on(FlagClause::BI_LEARNT);
                 }
 
                 if certification_store.is_active() {
@@ -930,12 +951,16 @@ impl ClauseDBIF for ClauseDB {
         let c = &mut self.clause[NonZeroU32::get(cid.ordinal) as usize];
         // Updating LBD at every analysis seems redundant.
         // But it's crucial. Don't remove the below.
+        let old_rank = c.rank as usize;
         let rank = c.update_lbd(asg, &mut self.lbd_temp);
         let learnt = c.is(FlagClause::LEARNT);
+        if learnt && rank < old_rank {
+            c.turn_on(FlagClause::PROTECTED);
+        }
         if learnt {
             #[cfg(feature = "just_used")]
             c.turn_on(FlagClause::USED);
-            #[cfg(feature = "clause_rewading")]
+            #[cfg(feature = "clause_rewarding")]
             self.reward_at_analysis(cid);
         }
         if 1 < rank {
@@ -945,14 +970,6 @@ impl ClauseDBIF for ClauseDB {
     }
     /// reduce the number of 'learnt' or *removable* clauses.
     fn reduce(&mut self, asg: &mut impl AssignIF, setting: ReductionType) {
-        impl Clause {
-            fn reverse_activity_sum(&self, asg: &impl AssignIF) -> f64 {
-                self.iter().map(|l| 1.0 - asg.activity(l.vi())).sum()
-            }
-            fn lbd(&self) -> f64 {
-                self.rank as f64
-            }
-        }
         let ClauseDB {
             ref mut clause,
             ref mut lbd_temp,
@@ -968,6 +985,8 @@ impl ClauseDBIF for ClauseDB {
 
         let mut perm: Vec<OrderedProxy<usize>> = Vec::with_capacity(clause.len());
         let mut alives = 0;
+        let mut num_protected = 0;
+        let mut oversized: Vec<usize> = Vec::new();
         for (i, c) in clause
             .iter_mut()
             .enumerate()
@@ -992,7 +1011,17 @@ impl ClauseDBIF for ClauseDB {
             if !c.is(FlagClause::LEARNT) {
                 continue;
             }
+            if c.is(FlagClause::OVERSIZED) {
+                oversized.push(i);
+                continue;
+            }
             alives += 1;
+            // Glucose-like protection: a clause whose LBD improved survives once.
+            if c.is(FlagClause::PROTECTED) {
+                c.turn_off(FlagClause::PROTECTED);
+                num_protected += 1;
+                continue;
+            }
             match setting {
                 ReductionType::RASonADD(_) => {
                     perm.push(OrderedProxy::new(i, c.reverse_activity_sum(asg)));
@@ -1003,7 +1032,7 @@ impl ClauseDBIF for ClauseDB {
                         perm.push(OrderedProxy::new(i, value));
                     }
                 }
-                ReductionType::LBDonADD(_) => {
+                ReductionType::LBDonADD(_) | ReductionType::KeepFraction(_) => {
                     perm.push(OrderedProxy::new(i, c.lbd()));
                 }
                 ReductionType::LBDonALL(cutoff, _) => {
@@ -1019,18 +1048,29 @@ impl ClauseDBIF for ClauseDB {
             ReductionType::RASonALL(_, scale) => (perm.len() as f64).powf(1.0 - scale) as usize,
             ReductionType::LBDonADD(size) => perm.len().saturating_sub(size),
             ReductionType::LBDonALL(_, scale) => (perm.len() as f64).powf(1.0 - scale) as usize,
+            // the fraction is of all alive learnts, including the protected ones.
+            ReductionType::KeepFraction(f) => {
+                ((alives as f64 * f.clamp(0.0, 1.0)) as usize).saturating_sub(num_protected)
+            }
         };
+        // protected clauses survive as well as the kept ones.
+        let survivors = keep + num_protected;
         self.reduction_threshold = match setting {
             ReductionType::RASonADD(_) | ReductionType::RASonALL(_, _) => {
-                keep as f64 / alives as f64
-            }
-            ReductionType::LBDonADD(_) | ReductionType::LBDonALL(_, _) => {
-                -(keep as f64) / alives as f64
+                survivors as f64 / alives as f64
             }
+            ReductionType::LBDonADD(_)
+            | ReductionType::LBDonALL(_, _)
+            | ReductionType::KeepFraction(_) => -(survivors as f64) / alives as f64,
         };
+        // `OrderedProxy` breaks ties by clause index; so the result is reproducible.
         perm.sort();
-        for i in perm.iter().skip(keep) {
-            self.remove_clause(ClauseId::from(i.to()));
+        for i in perm.iter().skip(keep).map(|i| i.to()).chain(oversized) {
+            let cid = ClauseId::from(i);
+            self.remove_clause(cid);
+            if self.recycle_clause_ids {
+                self.freelist.push(cid);
+            }
         }
     }
     fn reset(&mut self) {
@@ -1053,12 +1093,58 @@ impl ClauseDBIF for ClauseDB {
             }
         }
     }
+    fn remove_learnts(&mut self, asg: &impl AssignIF, born_after: Option<ClauseId>) {
+        let start = match born_after {
+            Some(cid) if !self.recycle_clause_ids => NonZeroU32::get(cid.ordinal) as usize + 1,
+            _ => 1,
+        };
+        for (i, c) in &mut self.clause.iter_mut().enumerate().skip(start) {
+            if !c.is(FlagClause::LEARNT) || c.is_dead() {
+                continue;
+            }
+            let cid = ClauseId::from(i);
+            let l0 = c.lit0();
+            if asg.assigned(l0) == Some(true)
+                && asg.reason(l0.vi()) == AssignReason::Implication(cid)
+            {
+                continue;
+            }
+            // A binary clause is a reason by `BinaryLink`, which holds the other literal.
+            if c.len() == 2 {
+                let l1 = c.lit1();
+                if (asg.assigned(l0) == Some(true)
+                    && asg.reason(l0.vi()) == AssignReason::BinaryLink(!l1))
+                    || (asg.assigned(l1) == Some(true)
+                        && asg.reason(l1.vi()) == AssignReason::BinaryLink(!l0))
+                {
+                    continue;
+                }
+            }
+            remove_clause_fn(
+                &mut self.certification_store,
+                &mut self.binary_link,
+                &mut self.watch_cache,
+                &mut self.num_bi_clause,
+                &mut self.num_clause,
+                &mut self.num_learnt,
+                cid,
+                c,
+            );
+        }
+        debug_assert_eq!(
+            self.num_clause,
+            self.clause.iter().skip(1).filter(|c| !c.is_dead()).count()
+        );
+    }
     fn certificate_add_assertion(&mut self, lit: Lit) {
         self.certification_store.add_clause(&[lit]);
     }
     fn certificate_save(&mut self) {
         self.certification_store.close();
     }
+    fn certificate_take(&mut self) -> Option<Vec<u8>> {
+        self.certification_store.take_proof()
+    }
     fn check_size(&self) -> Result<bool, SolverError> {
         if self.soft_limit == 0 || self.num_clause <= self.soft_limit {
             let nc = self.derefer(property::Tusize::NumClause);
@@ -1092,19 +1178,39 @@ impl ClauseDBIF for ClauseDB {
         }
         let l0 = vec[0];
         let mut num_sat = 0;
-        for (_, cid) in self.binary_link.connect_with(l0).iter() {
-            let c = &self.clause[NonZeroU32::get(cid.ordinal) as usize];
-            debug_assert!(c[0] == l0 || c[1] == l0);
-            let other = c[(c[0] == l0) as usize];
-            let vi = other.vi();
-            if self.lbd_temp[vi] == key && asg.assigned(other) == Some(true) {
-                num_sat += 1;
-                self.lbd_temp[vi] = key - 1;
+        // `!x` holds for each `x` in `frontier`; so does `other` in a bi-clause `[x, other]`.
+        let mut frontier: Vec<Lit> = vec![l0];
+        let mut visited: HashSet<VarId> = HashSet::new();
+        for depth in 1..=self.bi_clause_minimize_depth {
+            let mut next: Vec<Lit> = Vec::new();
+            for x in frontier.iter() {
+                for (_, cid) in self.binary_link.connect_with(*x).iter() {
+                    let c = &self.clause[NonZeroU32::get(cid.ordinal) as usize];
+                    debug_assert!(c[0] == *x || c[1] == *x);
+                    let other = c[(c[0] == *x) as usize];
+                    if asg.assigned(other) != Some(true) {
+                        continue;
+                    }
+                    let vi = other.vi();
+                    if self.lbd_temp[vi] == key {
+                        num_sat += 1;
+                        self.lbd_temp[vi] = key - 1;
+                    }
+                    if depth < self.bi_clause_minimize_depth && vi != l0.vi() && visited.insert(vi)
+                    {
+                        next.push(!other);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
             }
+            frontier = next;
         }
         if 0 < num_sat {
             self.lbd_temp[l0.vi()] = key;
             vec.retain(|l| self.lbd_temp[l.vi()] == key);
+            self.num_bi_minimized_lit += num_sat;
         }
     }
     fn complete_bi_clauses(&mut self, asg: &mut impl AssignIF) {
@@ -1231,6 +1337,228 @@ impl ClauseDBIF for ClauseDB {
 }
 
 impl ClauseDB {
+    /// return an estimation of the number of bytes used by clauses, watch caches and
+    /// binary links. It's a sum of capacities of vectors times the size of elements.
+    ///
+    ///```
+    /// use crate::{splr::config::Config, splr::types::*};
+    /// use crate::splr::cdb::{self, ClauseDB};
+    ///
+    /// let cnf = CNFDescription {
+    ///     num_of_variables: 4,
+    ///     ..CNFDescription::default()
+    /// };
+    /// let cdb = ClauseDB::instantiate(&Config::default(), &cnf);
+    /// assert!(0 < cdb.mem_bytes());
+    /// assert_eq!(cdb.derefer(cdb::property::Tusize::MemBytes), cdb.mem_bytes());
+    ///```
+    pub fn mem_bytes(&self) -> usize {
+        let clauses = self.clause.capacity() * std::mem::size_of::<Clause>()
+            + self
+                .clause
+                .iter()
+                .map(|c| c.lits.capacity() * std::mem::size_of::<Lit>())
+                .sum::<usize>();
+        let watches = self.watch_cache.capacity() * std::mem::size_of::<WatchCache>()
+            + self
+                .watch_cache
+                .iter()
+                .map(|w| w.capacity() * std::mem::size_of::<(ClauseId, Lit)>())
+                .sum::<usize>();
+        clauses + watches + self.binary_link.mem_bytes()
+    }
+    /// register a clause as a non-learnt one, which is never removed by reduction.
+    /// If the same binary clause exists as a learnt, it becomes non-learnt.
+    pub fn add_permanent_clause(
+        &mut self,
+        asg: &mut impl AssignIF,
+        vec: &mut Vec<Lit>,
+    ) -> RefClause {
+        let rc = self.new_clause(asg, vec, false);
+        if let RefClause::RegisteredClause(cid) = rc {
+            let c = &mut self.clause[NonZeroU32::get(cid.ordinal) as usize];
+            c.turn_off(FlagClause::BI_LEARNT);
+            if c.is(FlagClause::LEARNT) {
+                c.turn_off(FlagClause::LEARNT);
+                self.num_learnt -= 1;
+            }
+        }
+        rc
+    }
+    /// add a learnt clause of the given rank (LBD), like a clause imported from another
+    /// solver. A binary clause gets rank 1 regardless of `rank`.
+    pub fn add_learnt_clause(
+        &mut self,
+        asg: &mut impl AssignIF,
+        vec: &mut Vec<Lit>,
+        rank: u16,
+    ) -> RefClause {
+        let rc = self.new_clause(asg, vec, true);
+        if let RefClause::Clause(cid) = rc {
+            let c = &mut self.clause[NonZeroU32::get(cid.ordinal) as usize];
+            if c.is(FlagClause::LEARNT) {
+                if c.rank <= 2 {
+                    self.num_lbd2 -= 1;
+                }
+                c.rank = rank;
+                c.rank_old = rank;
+                if rank <= 2 {
+                    self.num_lbd2 += 1;
+                }
+            }
+        }
+        rc
+    }
+    /// return a handle to the clause at `cid`.
+    pub fn clause_ref(&self, cid: ClauseId) -> ClauseRef {
+        ClauseRef {
+            id: cid,
+            gen: self
+                .generation
+                .get(NonZeroU32::get(cid.ordinal) as usize)
+                .copied()
+                .unwrap_or(0),
+        }
+    }
+    /// return `true` if `cr` refers to an alive clause which it was made for.
+    pub fn is_valid_ref(&self, cr: &ClauseRef) -> bool {
+        let i = NonZeroU32::get(cr.id.ordinal) as usize;
+        i < self.clause.len() && !self.clause[i].is_dead() && *cr == self.clause_ref(cr.id)
+    }
+    fn bump_generation(&mut self, cid: ClauseId) {
+        let i = NonZeroU32::get(cid.ordinal) as usize;
+        if self.generation.len() <= i {
+            self.generation.resize(self.clause.len().max(i + 1), 0);
+        }
+        self.generation[i] = self.generation[i].wrapping_add(1);
+    }
+    /// return an iterator over the pairs of id and literals of all alive clauses,
+    /// including learnt ones.
+    ///
+    ///```
+    /// use crate::{splr::config::Config, splr::types::*};
+    /// use crate::splr::solver::Solver;
+    ///
+    /// let s = Solver::try_from((Config::default(), vec![vec![1, 2], vec![-1, 2, 3]].as_ref()))
+    ///     .expect("panic");
+    /// let mut clauses = s.cdb.iter_active().map(|(_, c)| c.len()).collect::<Vec<_>>();
+    /// clauses.sort();
+    /// assert_eq!(clauses, vec![2, 3]);
+    ///```
+    pub fn iter_active(&self) -> impl Iterator<Item = (ClauseId, &[Lit])> {
+        self.clause
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, c)| !c.is_dead())
+            .map(|(i, c)| (ClauseId::from(i), c.lits.as_slice()))
+    }
+    /// return the distribution of LBDs of the current learnt clauses:
+    /// `histogram[n]` is the number of learnt clauses with LBD `n`.
+    /// LBDs are the ones calculated at the last update; this doesn't recalculate them.
+    ///
+    ///```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from(std::path::Path::new("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert!(s.cdb.lbd_histogram().is_empty());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert_eq!(
+    ///     s.cdb.lbd_histogram().iter().sum::<usize>(),
+    ///     s.cdb.derefer(cdb::property::Tusize::NumLearnt),
+    /// );
+    ///```
+    pub fn lbd_histogram(&self) -> Vec<usize> {
+        let mut histogram: Vec<usize> = Vec::new();
+        for c in self
+            .clause
+            .iter()
+            .skip(1)
+            .filter(|c| !c.is_dead() && c.is(FlagClause::LEARNT))
+        {
+            let lbd = c.rank as usize;
+            if histogram.len() <= lbd {
+                histogram.resize(lbd + 1, 0);
+            }
+            histogram[lbd] += 1;
+        }
+        histogram
+    }
+    /// return the distribution of activities of the current learnt clauses over
+    /// `num_buckets` buckets evenly dividing the range \[0, 1\].
+    /// **Requires 'clause_rewarding' feature**
+    #[cfg(feature = "clause_rewarding")]
+    pub fn activity_histogram(&self, num_buckets: usize) -> Vec<usize> {
+        let mut histogram = vec![0; num_buckets];
+        if num_buckets == 0 {
+            return histogram;
+        }
+        for c in self
+            .clause
+            .iter()
+            .skip(1)
+            .filter(|c| !c.is_dead() && c.is(FlagClause::LEARNT))
+        {
+            // the same decay as `Clause::update_activity`, without updating the clause
+            let activity = c.reward
+                * self
+                    .activity_decay
+                    .powi(self.tick as i32 - c.timestamp as i32);
+            let i = (activity * num_buckets as f64) as usize;
+            histogram[i.min(num_buckets - 1)] += 1;
+        }
+        histogram
+    }
+    /// return the structural class of the alive clauses which aren't learnt, including
+    /// binary ones, by a single scan. `TwoSat` is checked first, then `Horn`, then `DualHorn`.
+    /// Root-level assignments aren't taken into account; call
+    /// [`remove_satisfied`](`crate::solver::Solver::remove_satisfied`) beforehand to
+    /// classify the residual formula.
+    ///
+    ///```
+    /// use crate::{splr::config::Config, splr::types::*};
+    /// use crate::splr::{cdb::FormulaClass, solver::Solver};
+    ///
+    /// let s = Solver::try_from((Config::default(), vec![vec![1, 2], vec![-1, -2]].as_ref()))
+    ///     .expect("panic");
+    /// assert_eq!(s.cdb.structural_class(), FormulaClass::TwoSat);
+    /// let s = Solver::try_from((Config::default(), vec![vec![-1, -2, 3], vec![-3, 1]].as_ref()))
+    ///     .expect("panic");
+    /// assert_eq!(s.cdb.structural_class(), FormulaClass::Horn);
+    /// let s = Solver::try_from((Config::default(), vec![vec![1, 2, -3], vec![-1, 3]].as_ref()))
+    ///     .expect("panic");
+    /// assert_eq!(s.cdb.structural_class(), FormulaClass::DualHorn);
+    /// let s = Solver::try_from((Config::default(), vec![vec![1, 2, -3], vec![-1, -2, 3]].as_ref()))
+    ///     .expect("panic");
+    /// assert_eq!(s.cdb.structural_class(), FormulaClass::General);
+    ///```
+    pub fn structural_class(&self) -> FormulaClass {
+        let mut two_sat = true;
+        let mut horn = true;
+        let mut dual_horn = true;
+        for c in
+            self.clause.iter().skip(1).filter(|c| {
+                !c.is_dead() && !c.is(FlagClause::LEARNT) && !c.is(FlagClause::BI_LEARNT)
+            })
+        {
+            let num_pos = c.lits.iter().filter(|l| bool::from(**l)).count();
+            two_sat &= c.lits.len() <= 2;
+            horn &= num_pos <= 1;
+            dual_horn &= c.lits.len() - num_pos <= 1;
+            if !two_sat && !horn && !dual_horn {
+                return FormulaClass::General;
+            }
+        }
+        if two_sat {
+            FormulaClass::TwoSat
+        } else if horn {
+            FormulaClass::Horn
+        } else if dual_horn {
+            FormulaClass::DualHorn
+        } else {
+            FormulaClass::General
+        }
+    }
     /// formula: -a => b and b => c implies -a => c
     /// clause: [a, b] and [-b, c] deduces [a, c]
     /// map: [a].get(b), [!b].get(c), [a].get(c)
@@ -1332,4 +1660,10 @@ impl Clause {
         }
         falsified
     }
+    fn reverse_activity_sum(&self, asg: &impl AssignIF) -> f64 {
+        self.iter().map(|l| 1.0 - asg.activity(l.vi())).sum()
+    }
+    fn lbd(&self) -> f64 {
+        self.rank as f64
+    }
 }
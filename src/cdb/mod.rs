@@ -141,6 +141,10 @@ pub trait ClauseDBIF:
     fn reduce(&mut self, asg: &mut impl AssignIF, setting: ReductionType);
     /// remove all learnt clauses.
     fn reset(&mut self);
+    /// remove all learnt clauses except those being used as a reason.
+    /// If `born_after` is given, the ones added before it are kept too, as long as
+    /// ids aren't recycled and so tell the order of addition.
+    fn remove_learnts(&mut self, asg: &impl AssignIF, born_after: Option<ClauseId>);
     /// update flags.
     /// return `true` if it's learnt.
     fn update_at_analysis(&mut self, asg: &impl AssignIF, cid: ClauseId) -> bool;
@@ -148,6 +152,8 @@ pub trait ClauseDBIF:
     fn certificate_add_assertion(&mut self, lit: Lit);
     /// save the certification record to a file.
     fn certificate_save(&mut self);
+    /// return the certification record kept in memory, after `certificate_save`.
+    fn certificate_take(&mut self) -> Option<Vec<u8>>;
     /// check the number of clauses
     /// * `Err(SolverError::OutOfMemory)` -- the db size is over the limit.
     /// * `Ok(true)` -- enough small
@@ -157,7 +163,8 @@ pub trait ClauseDBIF:
     /// Otherwise returns a clause which is not satisfiable under a given assignment.
     /// Clauses with an unassigned literal are treated as falsified in `strict` mode.
     fn validate(&self, model: &[Option<bool>], strict: bool) -> Option<ClauseId>;
-    /// minimize a clause.
+    /// minimize a clause by chains of binary clauses starting from the negation of `vec[0]`.
+    /// Their length is bounded by `Config::bin_minimize_depth`.
     fn minimize_with_bi_clauses(&mut self, asg: &impl AssignIF, vec: &mut Vec<Lit>);
     /// complete bi-clause network
     fn complete_bi_clauses(&mut self, asg: &mut impl AssignIF);
@@ -190,8 +197,17 @@ pub struct ClauseId {
     pub ordinal: NonZeroU32,
 }
 
+/// A handle to a clause, which stays distinguishable after its id is reused.
+/// `gen` counts removals of the clause at `id`; so a stale handle has an old one.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ClauseRef {
+    pub id: ClauseId,
+    pub gen: u32,
+}
+
 /// A representation of 'clause'
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(not(feature = "clause_rewarding"), derive(Eq))]
 pub struct Clause {
     /// The literals in a clause.
     lits: Vec<Lit>,
@@ -239,10 +255,16 @@ pub struct ClauseDB {
     watch_cache: Vec<WatchCache>,
     /// collected free clause ids.
     freelist: Vec<ClauseId>,
+    /// put the ids of clauses removed by `reduce` into `freelist` if true.
+    recycle_clause_ids: bool,
+    /// the number of removals of the clause at each id, used by `ClauseRef`.
+    generation: Vec<u32>,
     /// see unsat_certificate.rs
     certification_store: CertificationStore,
     /// a number of clauses to emit out-of-memory exception
     soft_limit: usize,
+    /// max length of binary implication chains used in `minimize_with_bi_clauses`
+    bi_clause_minimize_depth: usize,
     /// 'small' clause threshold
     co_lbd_bound: u16,
     // not in use
@@ -262,6 +284,8 @@ pub struct ClauseDB {
     activity_decay: f64,
     #[cfg(feature = "clause_rewarding")]
     activity_anti_decay: f64,
+    #[cfg(feature = "clause_rewarding")]
+    activity_reward: f64,
 
     //
     //## LBD
@@ -287,6 +311,8 @@ pub struct ClauseDB {
     num_reduction: usize,
     /// the number of reregistration of a bi-clause
     num_reregistration: usize,
+    /// the number of literals removed by `minimize_with_bi_clauses`
+    num_bi_minimized_lit: usize,
     /// Literal Block Entanglement
     /// EMA of LBD of clauses used in conflict analysis (dependency graph)
     lb_entanglement: Ema2,
@@ -309,6 +335,29 @@ pub enum ReductionType {
     LBDonADD(usize),
     /// weight by Literal Block Distance over all learnt clauses
     LBDonALL(u16, f64),
+    /// keep the given fraction of learnt clauses weighted by Literal Block Distance
+    KeepFraction(f64),
+}
+
+/// Structural class of the irredundant clauses, returned by
+/// [`structural_class`](`crate::cdb::ClauseDB::structural_class`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FormulaClass {
+    /// every clause has at most two literals.
+    TwoSat,
+    /// every clause has at most one positive literal.
+    Horn,
+    /// every clause has at most one negative literal.
+    DualHorn,
+    /// none of the above
+    General,
+}
+
+/// An iterator over the literals of alive clauses, including learnt ones,
+/// returned by `IntoIterator for &ClauseDB`.
+#[derive(Clone, Debug)]
+pub struct ActiveClauses<'a> {
+    iter: Iter<'a, Clause>,
 }
 
 pub mod property {
@@ -320,24 +369,28 @@ pub mod property {
         NumBiClause,
         NumBiClauseCompletion,
         NumBiLearnt,
+        NumBiMinimizedLit,
         NumClause,
         NumLBD2,
         NumLearnt,
         NumReduction,
         NumReRegistration,
         Timestamp,
+        MemBytes,
     }
 
-    pub const USIZES: [Tusize; 9] = [
+    pub const USIZES: [Tusize; 11] = [
         Tusize::NumBiClause,
         Tusize::NumBiClauseCompletion,
         Tusize::NumBiLearnt,
+        Tusize::NumBiMinimizedLit,
         Tusize::NumClause,
         Tusize::NumLBD2,
         Tusize::NumLearnt,
         Tusize::NumReduction,
         Tusize::NumReRegistration,
         Tusize::Timestamp,
+        Tusize::MemBytes,
     ];
 
     impl PropertyDereference<Tusize, usize> for ClauseDB {
@@ -348,6 +401,7 @@ pub mod property {
                 Tusize::NumBiClause => self.num_bi_clause,
                 Tusize::NumBiClauseCompletion => self.num_bi_clause_completion,
                 Tusize::NumBiLearnt => self.num_bi_learnt,
+                Tusize::NumBiMinimizedLit => self.num_bi_minimized_lit,
                 Tusize::NumLBD2 => self.num_lbd2,
                 Tusize::NumLearnt => self.num_learnt,
                 Tusize::NumReduction => self.num_reduction,
@@ -357,6 +411,7 @@ pub mod property {
                 Tusize::Timestamp => self.tick,
                 #[cfg(not(feature = "clause_rewarding"))]
                 Tusize::Timestamp => 0,
+                Tusize::MemBytes => self.mem_bytes(),
             }
         }
     }
@@ -407,7 +462,7 @@ pub mod property {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assign::{AssignStack, PropagateIF};
+    use crate::assign::{AssignStack, PropagateIF, VarManipulateIF};
 
     fn lit(i: i32) -> Lit {
         Lit::from(i)
@@ -426,6 +481,29 @@ mod tests {
         println!("pass to check watches");
     }
 
+    #[cfg(feature = "clause_rewarding")]
+    #[test]
+    fn test_reward_at_analysis() {
+        let config = Config::default();
+        let cnf = CNFDescription {
+            num_of_variables: 4,
+            ..CNFDescription::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        let mut cdb = ClauseDB::instantiate(&config, &cnf);
+        let c0 = cdb
+            .new_clause(&mut asg, &mut vec![lit(1), lit(2), lit(3)], false)
+            .as_cid();
+        let c1 = cdb
+            .new_clause(&mut asg, &mut vec![lit(-1), lit(2), lit(4)], true)
+            .as_cid();
+        cdb.update_activity_tick();
+        assert!(!cdb.update_at_analysis(&asg, c0));
+        assert!(cdb.update_at_analysis(&asg, c1));
+        // only learnt clauses are rewarded.
+        assert_eq!(cdb[c0].reward, 0.0);
+        assert!(0.0 < cdb[c1].reward);
+    }
     #[test]
     fn test_clause_instantiation() {
         let config = Config::default();
@@ -504,4 +582,320 @@ mod tests {
         assert_eq!(iter.next(), Some(&lit(3)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_minimize_with_bi_clause_chains() {
+        let cnf = CNFDescription {
+            num_of_variables: 4,
+            ..CNFDescription::default()
+        };
+        for (depth, expected) in [(1, vec![1, -3, -4]), (2, vec![1, -4])] {
+            let config = Config {
+                bin_minimize_depth: depth,
+                ..Config::default()
+            };
+            let mut asg = AssignStack::instantiate(&config, &cnf);
+            let mut cdb = ClauseDB::instantiate(&config, &cnf);
+            // !1 -> 2 -> 3
+            cdb.new_clause(&mut asg, &mut vec![lit(1), lit(2)], false);
+            cdb.new_clause(&mut asg, &mut vec![lit(-2), lit(3)], false);
+            for i in [-1, 2, 3, 4] {
+                asg.assign_by_decision(lit(i));
+            }
+            let mut vec = vec![lit(1), lit(-3), lit(-4)];
+            cdb.minimize_with_bi_clauses(&asg, &mut vec);
+            assert_eq!(
+                vec.iter().map(|l| i32::from(*l)).collect::<Vec<_>>(),
+                expected
+            );
+            assert_eq!(
+                cdb.derefer(property::Tusize::NumBiMinimizedLit),
+                3 - expected.len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_reduce_keeping_fraction() {
+        let config = Config::default();
+        let cnf = CNFDescription {
+            num_of_variables: 6,
+            ..CNFDescription::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        let mut cdb = ClauseDB::instantiate(&config, &cnf);
+        for i in 1..=4 {
+            cdb.new_clause(&mut asg, &mut vec![lit(i), lit(5), lit(-6)], true);
+        }
+        assert_eq!(cdb.derefer(property::Tusize::NumLearnt), 4);
+        cdb.reduce(&mut asg, ReductionType::KeepFraction(0.5));
+        assert_eq!(cdb.derefer(property::Tusize::NumLearnt), 2);
+        assert_eq!(cdb.derefer(property::Tf64::ReductionThreshold), -0.5);
+    }
+
+    #[test]
+    fn test_reduce_skips_protected_once() {
+        let config = Config::default();
+        let cnf = CNFDescription {
+            num_of_variables: 6,
+            ..CNFDescription::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        let mut cdb = ClauseDB::instantiate(&config, &cnf);
+        let cids = (1..=4)
+            .map(|i| {
+                cdb.new_clause(&mut asg, &mut vec![lit(i), lit(5), lit(-6)], true)
+                    .as_cid()
+            })
+            .collect::<Vec<_>>();
+        cdb[cids[0]].turn_on(FlagClause::PROTECTED);
+        cdb.reduce(&mut asg, ReductionType::KeepFraction(0.5));
+        // the protected one counts as one of the two survivors.
+        assert!(!cdb[cids[0]].is_dead());
+        assert!(!cdb[cids[0]].is(FlagClause::PROTECTED));
+        assert!(cids[1..3].iter().all(|cid| cdb[*cid].is_dead()));
+        assert!(!cdb[cids[3]].is_dead());
+        assert_eq!(cdb.derefer(property::Tf64::ReductionThreshold), -0.5);
+        // then it's eligible for the next reduction.
+        cdb.reduce(&mut asg, ReductionType::KeepFraction(0.0));
+        assert!(cdb[cids[0]].is_dead());
+        assert_eq!(cdb.derefer(property::Tusize::NumLearnt), 0);
+    }
+
+    #[test]
+    fn test_reduce_removes_oversized() {
+        let config = Config::default();
+        let cnf = CNFDescription {
+            num_of_variables: 6,
+            ..CNFDescription::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        let mut cdb = ClauseDB::instantiate(&config, &cnf);
+        let cids = (1..=4)
+            .map(|i| {
+                cdb.new_clause(&mut asg, &mut vec![lit(i), lit(5), lit(-6)], true)
+                    .as_cid()
+            })
+            .collect::<Vec<_>>();
+        cdb[cids[1]].turn_on(FlagClause::OVERSIZED);
+        cdb.reduce(&mut asg, ReductionType::KeepFraction(1.0));
+        assert!(cdb[cids[1]].is_dead());
+        assert!([0, 2, 3].iter().all(|i| !cdb[cids[*i]].is_dead()));
+        assert_eq!(cdb.derefer(property::Tusize::NumLearnt), 3);
+    }
+
+    #[test]
+    fn test_reduce_breaks_ties_by_clause_index() {
+        let config = Config::default();
+        let cnf = CNFDescription {
+            num_of_variables: 6,
+            ..CNFDescription::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        let mut cdb = ClauseDB::instantiate(&config, &cnf);
+        let cids = (1..=4)
+            .map(|i| {
+                cdb.new_clause(&mut asg, &mut vec![lit(i), lit(5), lit(-6)], true)
+                    .as_cid()
+            })
+            .collect::<Vec<_>>();
+        cdb.reduce(&mut asg, ReductionType::KeepFraction(0.5));
+        // all have the same LBD; the ones with larger indices survive.
+        assert!(cids[..2].iter().all(|cid| cdb[*cid].is_dead()));
+        assert!(cids[2..].iter().all(|cid| !cdb[*cid].is_dead()));
+    }
+
+    #[test]
+    fn test_recycle_clause_ids() {
+        let cnf = CNFDescription {
+            num_of_variables: 6,
+            ..CNFDescription::default()
+        };
+        for recycle in [true, false] {
+            let config = Config {
+                recycle_clause_ids: recycle,
+                ..Config::default()
+            };
+            let mut asg = AssignStack::instantiate(&config, &cnf);
+            let mut cdb = ClauseDB::instantiate(&config, &cnf);
+            let cids = (1..=4)
+                .map(|i| {
+                    cdb.new_clause(&mut asg, &mut vec![lit(i), lit(5), lit(-6)], true)
+                        .as_cid()
+                })
+                .collect::<Vec<_>>();
+            cdb.reduce(&mut asg, ReductionType::KeepFraction(0.5));
+            let cid = cdb
+                .new_clause(&mut asg, &mut vec![lit(1), lit(2), lit(6)], true)
+                .as_cid();
+            assert_eq!(cids[..2].contains(&cid), recycle);
+            assert_eq!(cids.iter().all(|c| *c < cid), !recycle);
+        }
+    }
+
+    #[test]
+    fn test_remove_learnts_keeps_reasons() {
+        let config = Config::default();
+        let cnf = CNFDescription {
+            num_of_variables: 6,
+            ..CNFDescription::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        let mut cdb = ClauseDB::instantiate(&config, &cnf);
+        let binary = cdb
+            .new_clause(&mut asg, &mut vec![lit(1), lit(2)], true)
+            .as_cid();
+        let ternary = cdb
+            .new_clause(&mut asg, &mut vec![lit(3), lit(-2), lit(1)], true)
+            .as_cid();
+        let other = cdb
+            .new_clause(&mut asg, &mut vec![lit(4), lit(5), lit(6)], true)
+            .as_cid();
+        asg.assign_by_decision(lit(-1));
+        assert!(asg.propagate(&mut cdb).is_ok());
+        assert_eq!(asg.reason(2), AssignReason::BinaryLink(lit(-1)));
+        assert_eq!(asg.reason(3), AssignReason::Implication(ternary));
+        cdb.remove_learnts(&asg, None);
+        assert!(!cdb[binary].is_dead());
+        assert!(!cdb[ternary].is_dead());
+        assert!(cdb[other].is_dead());
+    }
+
+    #[test]
+    fn test_remove_learnts_born_after() {
+        let cnf = CNFDescription {
+            num_of_variables: 6,
+            ..CNFDescription::default()
+        };
+        for recycle in [false, true] {
+            let config = Config {
+                recycle_clause_ids: recycle,
+                ..Config::default()
+            };
+            let mut asg = AssignStack::instantiate(&config, &cnf);
+            let mut cdb = ClauseDB::instantiate(&config, &cnf);
+            let older = cdb
+                .new_clause(&mut asg, &mut vec![lit(1), lit(2), lit(3)], true)
+                .as_cid();
+            let target = cdb
+                .new_clause(&mut asg, &mut vec![lit(-1), lit(4), lit(5)], false)
+                .as_cid();
+            let newer = cdb
+                .new_clause(&mut asg, &mut vec![lit(2), lit(4), lit(6)], true)
+                .as_cid();
+            cdb.remove_clause(target);
+            cdb.remove_learnts(&asg, Some(target));
+            assert_eq!(cdb[older].is_dead(), recycle);
+            assert!(cdb[newer].is_dead());
+        }
+    }
+
+    #[test]
+    fn test_clause_ref_over_recycled_id() {
+        let cnf = CNFDescription {
+            num_of_variables: 6,
+            ..CNFDescription::default()
+        };
+        let config = Config {
+            recycle_clause_ids: true,
+            ..Config::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        let mut cdb = ClauseDB::instantiate(&config, &cnf);
+        let refs = (1..=4)
+            .map(|i| {
+                let cid = cdb
+                    .new_clause(&mut asg, &mut vec![lit(i), lit(5), lit(-6)], true)
+                    .as_cid();
+                cdb.clause_ref(cid)
+            })
+            .collect::<Vec<_>>();
+        cdb.reduce(&mut asg, ReductionType::KeepFraction(0.5));
+        let cid = cdb
+            .new_clause(&mut asg, &mut vec![lit(1), lit(2), lit(6)], true)
+            .as_cid();
+        let stale = refs.iter().find(|r| r.id == cid).expect("not reused");
+        assert!(!cdb.is_valid_ref(stale));
+        assert!(cdb.is_valid_ref(&cdb.clause_ref(cid)));
+        assert!(refs[2..].iter().all(|r| cdb.is_valid_ref(r)));
+    }
+
+    #[cfg(feature = "clause_rewarding")]
+    #[test]
+    fn test_clause_reward_parameters() {
+        let cnf = CNFDescription {
+            num_of_variables: 3,
+            ..CNFDescription::default()
+        };
+        for (anti_decay, increment, expected) in
+            [(0.0, 1.0, 0.05), (0.5, 1.0, 0.5), (0.5, 4.0, 2.0)]
+        {
+            let config = Config {
+                crw_dcy_rat: 0.95,
+                crw_anti_dcy_rat: anti_decay,
+                crw_rwd_inc: increment,
+                ..Config::default()
+            };
+            let mut asg = AssignStack::instantiate(&config, &cnf);
+            let mut cdb = ClauseDB::instantiate(&config, &cnf);
+            let cid = cdb
+                .new_clause(&mut asg, &mut vec![lit(1), lit(2), lit(3)], true)
+                .as_cid();
+            cdb.update_activity_tick();
+            cdb.reward_at_analysis(cid);
+            assert!((cdb[cid].reward - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_transform_by_elimination_keeps_watch_caches() {
+        let config = Config::default();
+        let cnf = CNFDescription {
+            num_of_variables: 4,
+            ..CNFDescription::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        let mut cdb = ClauseDB::instantiate(&config, &cnf);
+        let c1 = cdb
+            .new_clause(&mut asg, &mut vec![lit(1), lit(2), lit(3), lit(4)], false)
+            .as_cid();
+        assert_eq!(
+            cdb.transform_by_elimination(c1, lit(1)),
+            RefClause::Clause(c1)
+        );
+        // every cached literal should be a literal in the clause.
+        for wc in cdb.watch_cache.iter() {
+            for (cid, cached) in wc.iter() {
+                assert!(cdb[*cid].iter().any(|l| l == cached));
+            }
+        }
+        // so a satisfied removed literal can't hide the clause from propagation.
+        for i in [1, -2, -3] {
+            asg.assign_by_decision(lit(i));
+            assert!(asg.propagate(&mut cdb).is_ok());
+        }
+        assert_eq!(asg.assigned(lit(4)), Some(true));
+    }
+    #[test]
+    fn test_structural_class_skips_learnts() {
+        let config = Config::default();
+        let cnf = CNFDescription {
+            num_of_variables: 4,
+            ..CNFDescription::default()
+        };
+        let mut asg = AssignStack::instantiate(&config, &cnf);
+        let mut cdb = ClauseDB::instantiate(&config, &cnf);
+        cdb.new_clause(&mut asg, &mut vec![lit(-1), lit(-2), lit(3)], false);
+        assert_eq!(cdb.structural_class(), FormulaClass::Horn);
+        // neither learnt binary clauses nor learnt ones break the class.
+        let binary = cdb
+            .new_clause(&mut asg, &mut vec![lit(1), lit(2)], true)
+            .as_cid();
+        assert!(!cdb[binary].is(FlagClause::LEARNT));
+        cdb.new_clause(&mut asg, &mut vec![lit(1), lit(2), lit(4)], true);
+        assert_eq!(cdb.structural_class(), FormulaClass::Horn);
+        // but a given binary clause does.
+        cdb.new_clause(&mut asg, &mut vec![lit(2), lit(4)], false);
+        assert_eq!(cdb.structural_class(), FormulaClass::General);
+    }
 }
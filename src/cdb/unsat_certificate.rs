@@ -26,11 +26,38 @@ impl CertificationStore {
     pub fn add_clause(&mut self, _clause: &[Lit]) {}
     pub fn delete_clause(&mut self, _vec: &[Lit]) {}
     pub fn close(&mut self) {}
+    pub fn take_proof(&mut self) -> Option<Vec<u8>> {
+        None
+    }
 }
 
 #[cfg(not(feature = "no_IO"))]
 const DUMP_INTERVAL: usize = 4096 * 16;
 
+#[cfg(not(feature = "no_IO"))]
+/// Destination of UNSAT certification
+#[derive(Debug)]
+enum ProofSink {
+    File(BufWriter<File>),
+    Memory(Vec<u8>),
+}
+
+#[cfg(not(feature = "no_IO"))]
+impl Write for ProofSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            ProofSink::File(w) => w.write(buf),
+            ProofSink::Memory(v) => v.write(buf),
+        }
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            ProofSink::File(w) => w.flush(),
+            ProofSink::Memory(_) => Ok(()),
+        }
+    }
+}
+
 #[cfg(not(feature = "no_IO"))]
 /// Struct for saving UNSAT certification
 #[derive(Debug, Default)]
@@ -38,7 +65,11 @@ pub struct CertificationStore {
     /// clause history to make certification
     queue: Vec<i32>,
     target: Option<PathBuf>,
-    buffer: Option<BufWriter<File>>,
+    buffer: Option<ProofSink>,
+    /// the closed certification kept in memory
+    proof: Option<Vec<u8>>,
+    /// write deletions or not; see `Config::proof_emit_deletions`
+    emit_deletions: bool,
 }
 
 impl Clone for CertificationStore {
@@ -51,13 +82,23 @@ impl Clone for CertificationStore {
 impl Instantiate for CertificationStore {
     fn instantiate(config: &Config, _cnf: &CNFDescription) -> Self {
         #[cfg(not(feature = "no_IO"))]
+        if config.use_certification && config.proof_in_memory {
+            return CertificationStore {
+                queue: Vec::with_capacity(DUMP_INTERVAL + 1024),
+                buffer: Some(ProofSink::Memory(Vec::new())),
+                emit_deletions: config.proof_emit_deletions,
+                ..CertificationStore::default()
+            };
+        }
         if config.use_certification {
             let cert: PathBuf = config.io_odir.join(&config.io_pfile);
             if let Ok(out) = File::create(&cert) {
                 return CertificationStore {
                     queue: Vec::with_capacity(DUMP_INTERVAL + 1024),
-                    buffer: Some(BufWriter::new(out)),
+                    buffer: Some(ProofSink::File(BufWriter::new(out))),
                     target: Some(cert),
+                    proof: None,
+                    emit_deletions: config.proof_emit_deletions,
                 };
             }
         }
@@ -80,6 +121,9 @@ impl CertificationStore {
         }
     }
     pub fn delete_clause(&mut self, clause: &[Lit]) {
+        if !self.emit_deletions {
+            return;
+        }
         self.queue.push((clause.len() as i32).neg());
         for l in clause.iter() {
             self.queue.push(i32::from(*l));
@@ -95,10 +139,16 @@ impl CertificationStore {
         self.dump_to_file();
         if let Some(ref mut buf) = self.buffer {
             let _ = buf.write_all(b"0\n");
-            self.buffer = None;
+            if let Some(ProofSink::Memory(v)) = self.buffer.take() {
+                self.proof = Some(v);
+            }
             self.target = None;
         }
     }
+    /// return the certification kept in memory, after `close`.
+    pub fn take_proof(&mut self) -> Option<Vec<u8>> {
+        self.proof.take()
+    }
 }
 
 #[cfg(not(feature = "no_IO"))]
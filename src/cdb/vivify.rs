@@ -23,10 +23,25 @@ impl VivifyIF for ClauseDB {
                 SolverError::RootLevelConflict(cc)
             })?;
         }
-        let mut clauses: Vec<OrderedProxy<ClauseId>> =
+        let clauses: Vec<OrderedProxy<ClauseId>> =
             select_targets(asg, self, state[Stat::Restart] == 0, NUM_TARGETS);
+        self.vivify_clauses(asg, state, clauses, usize::MAX)
+            .map(|_| ())
+    }
+}
+
+impl ClauseDB {
+    /// vivify `clauses` from the tail until the number of assignments by probing
+    /// reaches `budget`, then return the number of removed literals.
+    pub(crate) fn vivify_clauses(
+        &mut self,
+        asg: &mut AssignStack,
+        state: &mut State,
+        mut clauses: Vec<OrderedProxy<ClauseId>>,
+        budget: usize,
+    ) -> Result<usize, SolverError> {
         if clauses.is_empty() {
-            return Ok(());
+            return Ok(0);
         }
         let num_target = clauses.len();
         state[Stat::Vivification] += 1;
@@ -37,8 +52,13 @@ impl VivifyIF for ClauseDB {
         let mut num_check = 0;
         let mut num_shrink = 0;
         let mut num_assert = 0;
+        let mut num_propagation = 0;
+        let mut num_removed_lits = 0;
         let mut to_display = 0;
         'next_clause: while let Some(cp) = clauses.pop() {
+            if budget <= num_propagation {
+                break;
+            }
             asg.backtrack_sandbox();
             debug_assert_eq!(asg.decision_level(), asg.root_level());
             if asg.remains() {
@@ -66,6 +86,7 @@ impl VivifyIF for ClauseDB {
             num_check += 1;
             debug_assert!(clits.iter().all(|l| !clits.contains(&!*l)));
             let mut decisions: Vec<Lit> = Vec::new();
+            let mut stack_len = asg.stack_len();
             for lit in clits.iter().copied() {
                 // assert!(!asg.var(lit.vi()).is(FlagVar::ELIMINATED));
                 match asg.assigned(!lit) {
@@ -77,7 +98,10 @@ impl VivifyIF for ClauseDB {
                         decisions.push(!lit);
                         asg.assign_by_decision(!lit);
                         //## Rule 3
-                        if let Err(cc) = asg.propagate_sandbox(self) {
+                        let propagated = asg.propagate_sandbox(self);
+                        num_propagation += asg.stack_len() - stack_len;
+                        stack_len = asg.stack_len();
+                        if let Err(cc) = propagated {
                             let mut vec: Vec<Lit>;
                             match cc.1 {
                                 AssignReason::BinaryLink(l) => {
@@ -117,6 +141,7 @@ impl VivifyIF for ClauseDB {
                                     unreachable!("vivify")
                                 }
                             }
+                            num_removed_lits += clits.len().saturating_sub(vec.len());
                             match vec.len() {
                                 0 => {
                                     state.flush("");
@@ -176,7 +201,7 @@ impl VivifyIF for ClauseDB {
         // );
         state[Stat::VivifiedClause] += num_shrink;
         state[Stat::VivifiedVar] += num_assert;
-        Ok(())
+        Ok(num_removed_lits)
     }
 }
 
@@ -338,7 +363,7 @@ impl Clause {
             (!self.is_dead()
                 && self.rank * 2 <= self.rank_old
                 && (self.is(FlagClause::LEARNT) || self.is(FlagClause::DERIVE20)))
-            .then(|| self.reward)
+            .then_some(self.reward)
         }
     }
     #[cfg(not(feature = "clause_rewarding"))]
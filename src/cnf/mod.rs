@@ -1,13 +1,20 @@
 // pub mod cnf;
 // pub use self::cnf::*;
+use crate::{
+    solver::{Certificate, SatSolverIF, Solver},
+    types::Lit,
+};
 use std::{
     collections::HashSet,
     fs::File,
     io::{BufRead, BufReader},
+    ops::ControlFlow,
     path::Path,
 };
 
 const TOO_MANY_CLAUSES: usize = 100_000;
+/// `add_at_most_one` uses the pairwise encoding up to this number of literals.
+const PAIRWISE_AMO_LIMIT: usize = 6;
 
 pub type Clause = Vec<i32>;
 
@@ -17,7 +24,7 @@ pub enum CNFOperationError {
     AddingEmptyClause,
     AddignConflictingAssingment,
     ReadingCNFFile,
-    ParsingCNF,
+    ParsingCNF(String),
     CreatingCNFFile,
     WritingCNFFile,
     UnknownError(String),
@@ -30,6 +37,10 @@ pub struct CNF {
     clauses: Vec<Clause>,
     cls_map: HashSet<Vec<i32>>,
     no_check_uniqueness: bool,
+    /// comment lines before the header
+    header_comments: Vec<String>,
+    /// comment lines after the header, with the number of clauses preceding them
+    comments: Vec<(usize, String)>,
 }
 
 impl std::fmt::Display for CNF {
@@ -50,11 +61,34 @@ pub trait CnfIf: Sized {
     // - `None`: if the clause is in it already
     fn add_clause<C: AsRef<Clause>>(&mut self, clause: C) -> Result<&mut CNF, Self::Error>;
     fn from_vec_i32<V: AsRef<[Clause]>>(clauses: V) -> Result<Self, Self::Error>;
+    // Load a DIMACS file. The numbers of vars and clauses in the header aren't checked.
     fn load(file: &Path) -> Result<Self, Self::Error>;
+    // Same as `load` but returns `ParsingCNF` if the numbers of vars and clauses
+    // disagree with the header.
+    fn load_strict(file: &Path) -> Result<Self, Self::Error>;
+    // Same as `load` but keeps comment lines, which are re-emitted by `dump_to_string`
+    // at the same positions relative to the header and clauses.
+    fn load_with_comments(file: &Path) -> Result<Self, Self::Error>;
     fn num_vars(&self) -> u32;
     fn num_clauses(&self) -> usize;
     fn save(&self, file: &Path) -> Result<(), Self::Error>;
     fn dump_to_string(&self) -> String;
+    // Tseitin encoding of `out <-> inputs[0] & inputs[1] & ...`.
+    // Gate helpers allocate no var, and skip clauses which exist already.
+    fn add_and(&mut self, out: i32, inputs: &[i32]) -> Result<&mut CNF, Self::Error>;
+    // Tseitin encoding of `out <-> inputs[0] | inputs[1] | ...`.
+    fn add_or(&mut self, out: i32, inputs: &[i32]) -> Result<&mut CNF, Self::Error>;
+    // Tseitin encoding of `out <-> a ^ b`.
+    fn add_xor(&mut self, out: i32, a: i32, b: i32) -> Result<&mut CNF, Self::Error>;
+    // Tseitin encoding of `out <-> if sel { a } else { b }`.
+    fn add_ite(&mut self, out: i32, sel: i32, a: i32, b: i32) -> Result<&mut CNF, Self::Error>;
+    // Encode 'at most one of `lits` holds' and return the number of auxiliary vars.
+    // It uses the pairwise encoding for a few literals, the sequential one otherwise.
+    // Auxiliary vars are allocated after the largest var in `self` and `lits`.
+    fn add_at_most_one(&mut self, lits: &[i32]) -> Result<u32, Self::Error>;
+    // Encode 'at most `k` of `lits` hold' by a sequential counter (Sinz 2005)
+    // and return the number of auxiliary vars.
+    fn add_at_most_k(&mut self, lits: &[i32], k: usize) -> Result<u32, Self::Error>;
 }
 
 impl CnfIf for CNF {
@@ -111,12 +145,269 @@ impl CnfIf for CNF {
         Ok(cnf)
     }
     fn load(path: &Path) -> Result<Self, Self::Error> {
+        CNF::load_with(path, false, false)
+    }
+    fn load_strict(path: &Path) -> Result<Self, Self::Error> {
+        CNF::load_with(path, true, false)
+    }
+    fn load_with_comments(path: &Path) -> Result<Self, Self::Error> {
+        CNF::load_with(path, false, true)
+    }
+    fn num_vars(&self) -> u32 {
+        self.num_vars
+    }
+    fn num_clauses(&self) -> usize {
+        self.clauses.len()
+    }
+    fn save(&self, file: &Path) -> Result<(), Self::Error> {
+        use std::io::Write;
+        if let Ok(f) = File::create(file) {
+            let mut buf = std::io::BufWriter::new(f);
+            buf.write_all(self.dump_to_string().as_bytes())
+                .map_err(|_| CNFOperationError::WritingCNFFile)
+        } else {
+            Err(CNFOperationError::CreatingCNFFile)
+        }
+    }
+    fn dump_to_string(&self) -> String {
+        let mut out = String::new();
+        for c in self.header_comments.iter() {
+            out.push_str(c);
+            out.push('\n');
+        }
+        out.push_str(&format!("p cnf {} {}\n", self.num_vars, self.clauses.len()));
+        let mut comments = self.comments.iter().peekable();
+        for (i, cls) in self.clauses.iter().enumerate() {
+            while let Some((_, c)) = comments.next_if(|(pos, _)| *pos <= i) {
+                out.push_str(c);
+                out.push('\n');
+            }
+            for l in cls.iter() {
+                out.push_str(&format!("{l} "));
+            }
+            out.push_str("0\n");
+        }
+        for (_, c) in comments {
+            out.push_str(c);
+            out.push('\n');
+        }
+        out
+    }
+    fn add_and(&mut self, out: i32, inputs: &[i32]) -> Result<&mut CNF, Self::Error> {
+        let mut clauses = inputs.iter().map(|i| vec![-out, *i]).collect::<Vec<_>>();
+        clauses.push(
+            std::iter::once(out)
+                .chain(inputs.iter().map(|i| -*i))
+                .collect(),
+        );
+        self.add_clauses_if_new(clauses)
+    }
+    fn add_or(&mut self, out: i32, inputs: &[i32]) -> Result<&mut CNF, Self::Error> {
+        let mut clauses = inputs.iter().map(|i| vec![out, -*i]).collect::<Vec<_>>();
+        clauses.push(
+            std::iter::once(-out)
+                .chain(inputs.iter().copied())
+                .collect(),
+        );
+        self.add_clauses_if_new(clauses)
+    }
+    fn add_xor(&mut self, out: i32, a: i32, b: i32) -> Result<&mut CNF, Self::Error> {
+        self.add_clauses_if_new(vec![
+            vec![-out, a, b],
+            vec![-out, -a, -b],
+            vec![out, -a, b],
+            vec![out, a, -b],
+        ])
+    }
+    fn add_ite(&mut self, out: i32, sel: i32, a: i32, b: i32) -> Result<&mut CNF, Self::Error> {
+        self.add_clauses_if_new(vec![
+            vec![-sel, -a, out],
+            vec![-sel, a, -out],
+            vec![sel, -b, out],
+            vec![sel, b, -out],
+        ])
+    }
+    fn add_at_most_one(&mut self, lits: &[i32]) -> Result<u32, Self::Error> {
+        if PAIRWISE_AMO_LIMIT < lits.len() {
+            return self.add_at_most_k(lits, 1);
+        }
+        let mut clauses = Vec::new();
+        for (i, a) in lits.iter().enumerate() {
+            for b in lits[i + 1..].iter() {
+                clauses.push(vec![-*a, -*b]);
+            }
+        }
+        self.add_clauses_if_new(clauses)?;
+        Ok(0)
+    }
+    fn add_at_most_k(&mut self, lits: &[i32], k: usize) -> Result<u32, Self::Error> {
+        let n = lits.len();
+        if n <= k {
+            return Ok(0);
+        }
+        if k == 0 {
+            self.add_clauses_if_new(lits.iter().map(|l| vec![-*l]).collect())?;
+            return Ok(0);
+        }
+        let base = lits
+            .iter()
+            .map(|l| l.unsigned_abs())
+            .max()
+            .map_or(self.num_vars, |m| m.max(self.num_vars)) as i32;
+        // `s(i, j)` means that at least `j + 1` of `lits[0..=i]` hold.
+        let s = |i: usize, j: usize| base + 1 + (i * k + j) as i32;
+        let mut clauses = vec![vec![-lits[0], s(0, 0)]];
+        for j in 1..k {
+            clauses.push(vec![-s(0, j)]);
+        }
+        for (i, x) in lits.iter().enumerate().take(n - 1).skip(1) {
+            clauses.push(vec![-*x, s(i, 0)]);
+            clauses.push(vec![-s(i - 1, 0), s(i, 0)]);
+            for j in 1..k {
+                clauses.push(vec![-*x, -s(i - 1, j - 1), s(i, j)]);
+                clauses.push(vec![-s(i - 1, j), s(i, j)]);
+            }
+            clauses.push(vec![-*x, -s(i - 1, k - 1)]);
+        }
+        clauses.push(vec![-lits[n - 1], -s(n - 2, k - 1)]);
+        self.add_clauses_if_new(clauses)?;
+        let num_aux = ((n - 1) * k) as u32;
+        self.num_vars = self.num_vars.max(base as u32 + num_aux);
+        Ok(num_aux)
+    }
+}
+
+/// Parse a CNF file lazily and call `f` on each clause until it returns `Break`.
+/// Unlike `CNF::load`, this doesn't hold clauses; the slice passed to `f` is a scratch
+/// buffer reused for the next clause. Comment lines and the header are skipped, and
+/// a clause can span multiple lines.
+pub fn for_each_clause<F>(path: &Path, mut f: F) -> Result<(), CNFOperationError>
+where
+    F: FnMut(&[i32]) -> ControlFlow<()>,
+{
+    let fs = File::open(path).map_err(|_| CNFOperationError::ReadingCNFFile)?;
+    read_clauses(BufReader::new(fs), false, |_, lits| f(lits))
+}
+
+/// Run an incremental CNF (`.icnf`) file on `solver`: clause lines are added by
+/// `add_clause`, and each `a <assumptions> 0` line is a query answered by
+/// `Solver::solve_under_assumptions`. The verdicts of the queries are returned in
+/// order. Vars beyond the current ones are added.
+pub fn run_icnf(path: &Path, solver: &mut Solver) -> Result<Vec<Certificate>, CNFOperationError> {
+    let fs = File::open(path).map_err(|_| CNFOperationError::ReadingCNFFile)?;
+    // a clause falsified at the root level makes all the following queries UNSAT.
+    let mut inconsistent = false;
+    let mut verdicts: Vec<Certificate> = Vec::new();
+    let mut error: Option<CNFOperationError> = None;
+    read_clauses(BufReader::new(fs), true, |is_query, lits| {
+        let max_vi = lits.iter().map(|l| l.unsigned_abs()).max().unwrap_or(0);
+        while solver.asg.num_vars < max_vi as usize {
+            solver.add_var();
+        }
+        if !is_query {
+            inconsistent |= solver.add_clause(lits).is_err();
+            return ControlFlow::Continue(());
+        }
+        if inconsistent {
+            verdicts.push(Certificate::UNSAT);
+            return ControlFlow::Continue(());
+        }
+        for l in lits.iter() {
+            solver.push_assumption(Lit::from(*l));
+        }
+        let result = solver.solve_under_assumptions();
+        while solver.pop_assumption().is_some() {}
+        match result {
+            Ok(verdict) => {
+                verdicts.push(verdict);
+                ControlFlow::Continue(())
+            }
+            Err(e) => {
+                error = Some(CNFOperationError::UnknownError(format!("{e}")));
+                ControlFlow::Break(())
+            }
+        }
+    })?;
+    error.map_or(Ok(verdicts), Err)
+}
+
+/// The clause reader shared by `for_each_clause` and `run_icnf`. It calls `f` on each
+/// clause with a flag which is `true` if the clause is a query line `a <lits> 0`;
+/// such lines are rejected unless `with_queries`.
+fn read_clauses<F>(
+    mut reader: impl BufRead,
+    with_queries: bool,
+    mut f: F,
+) -> Result<(), CNFOperationError>
+where
+    F: FnMut(bool, &[i32]) -> ControlFlow<()>,
+{
+    let mut buf = String::new();
+    let mut lits: Vec<i32> = Vec::new();
+    let mut is_query = false;
+    loop {
+        buf.clear();
+        match reader.read_line(&mut buf) {
+            Ok(0) => break,
+            Ok(_) if buf.trim_start().starts_with(['c', 'p']) => (),
+            // the end marker used in SATLIB
+            Ok(_) if buf.trim_start().starts_with('%') => break,
+            Ok(_) => {
+                let mut segs = buf.split_whitespace().peekable();
+                if with_queries && segs.next_if_eq(&"a").is_some() {
+                    is_query = true;
+                }
+                for seg in segs {
+                    match seg.parse::<i32>() {
+                        Ok(0) => {
+                            if f(is_query, &lits).is_break() {
+                                return Ok(());
+                            }
+                            lits.clear();
+                            is_query = false;
+                        }
+                        Ok(l) => lits.push(l),
+                        Err(_) => {
+                            return Err(CNFOperationError::ParsingCNF(format!(
+                                "invalid literal: {seg}"
+                            )))
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                return Err(CNFOperationError::UnknownError(format!("IOError ({e:?})")));
+            }
+        }
+    }
+    if !lits.is_empty() {
+        let _ = f(is_query, &lits);
+    }
+    Ok(())
+}
+
+impl CNF {
+    fn add_clauses_if_new(&mut self, clauses: Vec<Clause>) -> Result<&mut CNF, CNFOperationError> {
+        for c in clauses {
+            match self.add_clause(c) {
+                Ok(_) | Err(CNFOperationError::AddingClauseExists) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(self)
+    }
+    fn load_with(
+        path: &Path,
+        strict: bool,
+        preserve_comments: bool,
+    ) -> Result<Self, CNFOperationError> {
         let fs = File::open(path).map_err(|_| CNFOperationError::ReadingCNFFile)?;
         let mut reader = BufReader::new(fs);
         let mut buf = String::new();
         let mut nv: u32 = 0;
         let mut nc: usize = 0;
         let mut num_clause: usize = 0;
+        let mut num_parsed: usize = 0;
         let mut found_valid_header = false;
         let mut clause_extists_already = false;
         let mut cnf = CNF::default();
@@ -124,8 +415,17 @@ impl CnfIf for CNF {
             buf.clear();
             match reader.read_line(&mut buf) {
                 Ok(0) => break,
-                Ok(_) if buf == "\n" => (),
-                Ok(_) if buf.starts_with('c') => (),
+                Ok(_) if buf.trim().is_empty() => (),
+                Ok(_) if buf.trim_start().starts_with('c') => {
+                    if preserve_comments {
+                        let line = buf.trim_end().to_string();
+                        if found_valid_header {
+                            cnf.comments.push((cnf.clauses.len(), line));
+                        } else {
+                            cnf.header_comments.push(line);
+                        }
+                    }
+                }
                 Ok(_) if found_valid_header => {
                     let mut vec: Vec<i32> = Vec::new();
                     for seg in buf.split(' ') {
@@ -137,6 +437,7 @@ impl CnfIf for CNF {
                         }
                     }
                     debug_assert!(!vec.is_empty());
+                    num_parsed += 1;
                     if let Err(e) = cnf.add_clause(vec) {
                         if e == CNFOperationError::AddingClauseExists {
                             clause_extists_already = true;
@@ -166,56 +467,24 @@ impl CnfIf for CNF {
             }
         }
         if !found_valid_header {
-            return Err(CNFOperationError::ParsingCNF);
-        }
-        if cnf.num_vars() != nv {
-            println!("Warning: there are less variables than its declaration.");
+            return Err(CNFOperationError::ParsingCNF("no header".to_string()));
         }
-        if clause_extists_already {
-            assert_eq!(cnf.num_clauses(), num_clause);
-            println!("Warning: there are less clauses than its declaration.");
-        } else if cnf.num_clauses() < TOO_MANY_CLAUSES {
-            assert_eq!(cnf.num_clauses(), nc);
+        if strict && (cnf.num_vars() != nv || num_parsed != nc) {
+            return Err(CNFOperationError::ParsingCNF(format!(
+                "expected {nv} vars and {nc} clauses, but found {} vars and {num_parsed} clauses",
+                cnf.num_vars()
+            )));
         }
+        // Unlike `load_strict`, mismatches with the header are accepted silently.
+        debug_assert!(!clause_extists_already || cnf.num_clauses() == num_clause);
         Ok(cnf)
     }
-    fn num_vars(&self) -> u32 {
-        self.num_vars
-    }
-    fn num_clauses(&self) -> usize {
-        self.clauses.len()
-    }
-    fn save(&self, file: &Path) -> Result<(), Self::Error> {
-        use std::io::Write;
-        if let Ok(f) = File::create(file) {
-            let mut buf = std::io::BufWriter::new(f);
-            buf.write_all(self.dump_to_string().as_bytes())
-                .map_err(|_| CNFOperationError::WritingCNFFile)
-        } else {
-            Err(CNFOperationError::CreatingCNFFile)
-        }
-    }
-    fn dump_to_string(&self) -> String {
-        format!(
-            "p cnf {} {}\n{} 0\n",
-            self.num_vars,
-            self.clauses.len(),
-            self.clauses
-                .iter()
-                .map(|cls| cls
-                    .iter()
-                    .map(|ch| format!("{ch}"))
-                    .collect::<Vec<_>>()
-                    .join(" "))
-                .collect::<Vec<_>>()
-                .join(" 0\n"),
-        )
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{CNFDescription, Instantiate};
     use std::path::Path;
 
     #[test]
@@ -236,6 +505,21 @@ mod tests {
         assert_eq!(line.next(), Some("-1 -4 3 0"));
     }
     #[test]
+    fn test_dump_to_string() {
+        let cnf = CNF::from_vec_i32(Vec::<Vec<i32>>::new()).expect("panic");
+        assert_eq!(cnf.dump_to_string(), "p cnf 0 0\n");
+        let cnf = CNF::from_vec_i32(vec![vec![-2, 1]]).expect("panic");
+        assert_eq!(cnf.dump_to_string(), "p cnf 2 1\n-2 1 0\n");
+        // round trip
+        let path = std::env::temp_dir().join(format!("splr-dump-{}.cnf", std::process::id()));
+        for cnf in [CNF::default(), cnf] {
+            cnf.save(&path).expect("can't save");
+            let loaded = CNF::load(&path).expect("can't load");
+            assert_eq!(loaded.dump_to_string(), cnf.dump_to_string());
+        }
+        std::fs::remove_file(&path).expect("can't remove");
+    }
+    #[test]
     fn test_load_uf8() {
         let build = CNF::load(Path::new("cnfs/uf8.cnf"));
         dbg!(build.is_ok());
@@ -251,4 +535,166 @@ mod tests {
         assert_eq!(cnf.num_clauses(), 1065);
         assert_eq!(cnf.num_vars(), 250);
     }
+    #[test]
+    fn test_load_strict() {
+        assert!(CNF::load_strict(Path::new("cnfs/sample.cnf")).is_ok());
+        let path = std::env::temp_dir().join(format!("splr-strict-{}.cnf", std::process::id()));
+        std::fs::write(&path, "p cnf 4 3\n  c indented\n1 2 0\n-1 3 0\n").expect("can't write");
+        assert!(CNF::load(&path).is_ok());
+        let res = CNF::load_strict(&path);
+        std::fs::remove_file(&path).expect("can't remove");
+        assert_eq!(
+            res,
+            Err(CNFOperationError::ParsingCNF(
+                "expected 4 vars and 3 clauses, but found 3 vars and 2 clauses".to_string()
+            ))
+        );
+    }
+    #[test]
+    fn test_load_with_comments() {
+        let path = std::env::temp_dir().join(format!("splr-comments-{}.cnf", std::process::id()));
+        let text = "c header\np cnf 3 2\nc var 2 = output_enable\n1 2 0\n-1 3 0\nc end\n";
+        std::fs::write(&path, text).expect("can't write");
+        let plain = CNF::load(&path).expect("can't load");
+        let cnf = CNF::load_with_comments(&path).expect("can't load");
+        std::fs::remove_file(&path).expect("can't remove");
+        assert_eq!(plain.dump_to_string(), "p cnf 3 2\n1 2 0\n-1 3 0\n");
+        assert_eq!(cnf.dump_to_string(), text);
+    }
+    #[test]
+    fn test_run_icnf() {
+        let path = std::env::temp_dir().join(format!("splr-run-{}.icnf", std::process::id()));
+        let text = "p inccnf\n1 2 0\n-1 2 0\na -2 0\na 3 0\n-2 -3 0\na 3 0\na 0\n-2 0\na 0\n";
+        std::fs::write(&path, text).expect("can't write");
+        let config = crate::config::Config {
+            quiet_mode: true,
+            ..Default::default()
+        };
+        let mut solver = Solver::instantiate(&config, &CNFDescription::default());
+        let res = run_icnf(&path, &mut solver);
+        // a query line isn't a clause in DIMACS.
+        assert!(matches!(
+            for_each_clause(&path, |_| ControlFlow::Continue(())),
+            Err(CNFOperationError::ParsingCNF(_))
+        ));
+        std::fs::remove_file(&path).expect("can't remove");
+        let verdicts = res.expect("can't run");
+        assert_eq!(verdicts.len(), 5);
+        assert_eq!(verdicts[0], Certificate::UNSAT);
+        assert!(matches!(verdicts[1], Certificate::SAT(_)));
+        assert_eq!(verdicts[2], Certificate::UNSAT);
+        assert!(matches!(verdicts[3], Certificate::SAT(_)));
+        assert_eq!(verdicts[4], Certificate::UNSAT);
+    }
+    /// check `cnf` under every assignment of vars `1..=n` against `gate`,
+    /// which computes the value of var 1 from the others.
+    fn check_gate(cnf: &CNF, n: u32, gate: impl Fn(&[bool]) -> bool) {
+        for bits in 0..(1_u32 << n) {
+            let val = (0..=n)
+                .map(|i| 0 < i && bits & (1 << (i - 1)) != 0)
+                .collect::<Vec<_>>();
+            let sat = cnf.clauses.iter().all(|c| {
+                c.iter()
+                    .any(|l| val[l.unsigned_abs() as usize] == l.is_positive())
+            });
+            assert_eq!(sat, val[1] == gate(&val), "{val:?}");
+        }
+    }
+    #[test]
+    fn test_gates() {
+        let mut cnf = CNF::default();
+        cnf.add_and(1, &[2, -3, 4]).expect("failed");
+        check_gate(&cnf, 4, |v| v[2] && !v[3] && v[4]);
+        let mut cnf = CNF::default();
+        cnf.add_or(1, &[-2, 3, 4]).expect("failed");
+        check_gate(&cnf, 4, |v| !v[2] || v[3] || v[4]);
+        let mut cnf = CNF::default();
+        cnf.add_xor(1, 2, -3).expect("failed");
+        // x ^ !y == (x == y)
+        check_gate(&cnf, 3, |v| v[2] == v[3]);
+        let mut cnf = CNF::default();
+        cnf.add_ite(1, 2, 3, -4).expect("failed");
+        check_gate(&cnf, 4, |v| if v[2] { v[3] } else { !v[4] });
+        assert_eq!(cnf.num_vars(), 4);
+        // duplicated gates are fine.
+        assert!(cnf.add_ite(1, 2, 3, -4).is_ok());
+        assert_eq!(cnf.num_clauses(), 4);
+    }
+    /// check that the projection of the models of `cnf` to the first `n` vars is
+    /// equal to the assignments satisfying `pred`.
+    fn check_projection(cnf: &CNF, n: u32, pred: impl Fn(&[bool]) -> bool) {
+        let nv = cnf.num_vars();
+        let mut projected = vec![false; 1 << n];
+        for bits in 0..(1_u32 << nv) {
+            let val = (0..=nv)
+                .map(|i| 0 < i && bits & (1 << (i - 1)) != 0)
+                .collect::<Vec<_>>();
+            if cnf.clauses.iter().all(|c| {
+                c.iter()
+                    .any(|l| val[l.unsigned_abs() as usize] == l.is_positive())
+            }) {
+                projected[(bits & ((1 << n) - 1)) as usize] = true;
+            }
+        }
+        for (bits, found) in projected.iter().enumerate() {
+            let val = (0..=n)
+                .map(|i| 0 < i && bits & (1 << (i - 1)) != 0)
+                .collect::<Vec<_>>();
+            assert_eq!(*found, pred(&val), "{val:?}");
+        }
+    }
+    #[test]
+    fn test_cardinality_constraints() {
+        let count = |v: &[bool], n: usize| v[1..=n].iter().filter(|b| **b).count();
+        for n in [4, 8] {
+            let mut cnf = CNF::default();
+            let lits = (1..=n as i32).collect::<Vec<_>>();
+            let aux = cnf.add_at_most_one(&lits).expect("failed");
+            assert_eq!(aux, if n <= PAIRWISE_AMO_LIMIT { 0 } else { n - 1 } as u32);
+            assert_eq!(cnf.num_vars(), n as u32 + aux);
+            check_projection(&cnf, n as u32, |v| count(v, n) <= 1);
+        }
+        for k in 0..=5 {
+            let mut cnf = CNF::default();
+            let aux = cnf.add_at_most_k(&[1, 2, 3, 4, 5], k).expect("failed");
+            assert_eq!(aux, (4 * k * (k < 5) as usize) as u32);
+            if cnf.num_vars() == 0 {
+                continue;
+            }
+            check_projection(&cnf, 5, |v| count(v, 5) <= k);
+        }
+    }
+    #[test]
+    fn test_for_each_clause() {
+        use std::io::Write;
+        let path =
+            std::env::temp_dir().join(format!("splr-for-each-clause-{}.cnf", std::process::id()));
+        let n = 200_000;
+        {
+            let mut buf = std::io::BufWriter::new(File::create(&path).expect("can't create"));
+            writeln!(buf, "c generated\np cnf {n} {n}\n  c indented").expect("can't write");
+            for i in 1..n {
+                writeln!(buf, "{i} -{} 0", i + 1).expect("can't write");
+            }
+            // a clause spanning two lines
+            writeln!(buf, "{n}\n1 0").expect("can't write");
+        }
+        let mut num_clauses = 0;
+        let mut num_lits = 0;
+        let res = for_each_clause(&path, |lits| {
+            num_clauses += 1;
+            num_lits += lits.len();
+            ControlFlow::Continue(())
+        });
+        assert_eq!(res, Ok(()));
+        assert_eq!((num_clauses, num_lits), (n, 2 * n));
+        let mut first = Vec::new();
+        let res = for_each_clause(&path, |lits| {
+            first.extend_from_slice(lits);
+            ControlFlow::Break(())
+        });
+        std::fs::remove_file(&path).expect("can't remove");
+        assert_eq!(res, Ok(()));
+        assert_eq!(first, vec![1, -2]);
+    }
 }
@@ -1,16 +1,120 @@
 /// Module `config` provides solver's configuration and CLI.
-use {crate::types::DecisionLevel, std::path::PathBuf};
+use {
+    crate::types::{CNFIndicator, DecisionLevel},
+    std::path::PathBuf,
+};
 
 pub const CERTIFICATION_DEFAULT_FILENAME: &str = "proof.drat";
 
-/// Configuration built from command line options
+/// The polarity of decision vars set at every rephasing, which requires feature `rephase`.
+///
+/// # Example
+///
+/// ```
+/// use crate::splr::*;
+/// use crate::splr::config::RephasePolicy;
+///
+/// for policy in [RephasePolicy::LastPhase, RephasePolicy::Random, RephasePolicy::Inverted] {
+///     let config = Config {
+///         rephase_policy: policy,
+///         quiet_mode: true,
+///         ..Config::from("cnfs/uf100-010.cnf")
+///     };
+///     let mut s = Solver::build(&config).expect("can't load");
+///     assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RephasePolicy {
+    /// keep the saved phases
+    LastPhase,
+    /// use the best assignment found so far
+    #[default]
+    BestPhase,
+    /// use pseudo random phases
+    Random,
+    /// flip the saved phases
+    Inverted,
+}
+
+/// The polarity given to decision vars before the first search.
+///
+/// # Example
+///
+/// ```
+/// use crate::splr::*;
+/// use crate::splr::config::InitialPolarity;
+///
+/// for polarity in [
+///     InitialPolarity::True,
+///     InitialPolarity::False,
+///     InitialPolarity::Random(7),
+///     InitialPolarity::Jeroslow,
+/// ] {
+///     let config = Config {
+///         initial_polarity: polarity,
+///         quiet_mode: true,
+///         ..Config::from("cnfs/uf100-010.cnf")
+///     };
+///     let mut s = Solver::build(&config).expect("can't load");
+///     assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InitialPolarity {
+    /// the phases set by the preprocessor from the numbers of occurrences
+    #[default]
+    OccurrenceBias,
+    /// all positive
+    True,
+    /// all negative
+    False,
+    /// pseudo random phases generated from the seed
+    Random(u64),
+    /// the literal with the larger Jeroslow-Wang score, the sum of `2^-len` over
+    /// the clauses containing it
+    Jeroslow,
+}
+
+/// The order of literals in `Certificate::SAT`.
+///
+/// # Example
+///
+/// ```
+/// use crate::splr::*;
+/// use crate::splr::config::ModelOrder;
+///
+/// let config = Config {
+///     model_order: ModelOrder::InputOrder,
+///     ..Config::default()
+/// };
+/// let clauses = vec![vec![3, -1], vec![-3], vec![2, 1]];
+/// let mut s = Solver::try_from((config, clauses.as_ref())).expect("panic");
+/// assert_eq!(s.solve(), Ok(Certificate::SAT(vec![-3, -1, 2])));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModelOrder {
+    /// ascending var index
+    #[default]
+    VarIndex,
+    /// the order of the first appearance in the given clauses; other vars follow them
+    InputOrder,
+}
+
+/// Configuration built from command line options.
+/// With feature `serde`, missing fields are filled by the default values on deserialization.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Config {
     //
     //## solver configuration
     //
-    /// Dec. lvl to use chronoBT
-    pub c_cbt_thr: DecisionLevel,
+    /// Min backjump distance to use chronoBT; `None` disables chronoBT
+    pub chrono_bt_threshold: Option<DecisionLevel>,
 
     /// Soft limit of #clauses (6MC/GB)
     pub c_cls_lim: usize,
@@ -18,6 +122,19 @@ pub struct Config {
     /// CPU time limit in sec.
     pub c_timeout: f64,
 
+    /// Max #conflicts per `solve` call; stops with `Certificate::UNKNOWN`
+    pub max_conflicts: Option<usize>,
+
+    /// Max #propagations per `solve` call; stops with `Certificate::UNKNOWN`
+    pub max_propagations: Option<usize>,
+
+    /// Drops duplicate clauses while loading a CNF, unless it has too many clauses
+    pub dedup_clauses: bool,
+
+    /// Rejects a clause with a var beyond the number of vars in the CNF header as
+    /// `InvalidLiteral`; otherwise the solver grows to hold it
+    pub strict_var_range: bool,
+
     //
     //## I/O configuration
     //
@@ -27,6 +144,9 @@ pub struct Config {
     /// DIMACS CNF file
     pub cnf_file: PathBuf,
 
+    /// DIMACS CNF text used instead of `cnf_file`; see `Config::from_cnf_str`
+    pub cnf_text: Option<String>,
+
     /// Output directory
     pub io_odir: PathBuf,
 
@@ -36,6 +156,12 @@ pub struct Config {
     /// Result filename/stdout
     pub io_rfile: PathBuf,
 
+    /// Residual CNF filename written on timeout, including learnt clauses
+    pub dump_on_timeout: Option<PathBuf>,
+
+    /// The order of literals in a model
+    pub model_order: ModelOrder,
+
     /// Disable coloring
     pub no_color: bool,
 
@@ -48,20 +174,57 @@ pub struct Config {
     /// Writes a DRAT UNSAT certification file
     pub use_certification: bool,
 
+    /// Keeps the DRAT certification in memory instead of a file;
+    /// see [`Solver::solve_with_proof`](`crate::solver::Solver::solve_with_proof`)
+    pub proof_in_memory: bool,
+
+    /// Writes clause deletions to the DRAT certification. Without them the proof is
+    /// still valid DRAT, though a checker has to keep every clause, so checking gets
+    /// slower and weaker.
+    pub proof_emit_deletions: bool,
+
     /// Uses Glucose-like progress report
     pub use_log: bool,
 
+    /// Records search events for `Solver::trace`
+    pub record_trace: bool,
+
+    /// Checks a model against all given clauses before returning it
+    pub self_check: bool,
+
     //
     //## clause management
     //
     // clause reward dacay rate
     pub crw_dcy_rat: f64,
+    /// Clause reward anti-decay rate, the weight of a reward; 0.0, the default, uses
+    /// `1.0 - crw_dcy_rat`, the behavior of a single `crw_dcy_rat`
+    pub crw_anti_dcy_rat: f64,
+    /// Clause reward given at each use in conflict analysis, scaled by `crw_anti_dcy_rat`
+    pub crw_rwd_inc: f64,
     // clause reduction LBD threshold for mode 2: exploration
     pub cls_rdc_lbd: u16,
     // clause reduction ratio for mode 1: exploitation
     pub cls_rdc_rm1: f64,
     // clause reduction ratio for mode 2: exploration
     pub cls_rdc_rm2: f64,
+    /// #conflicts before the first Glucose-like reduction, which halves learnt clauses
+    /// at a restart. 0 (default) keeps the stage-driven reductions.
+    pub first_reduction: usize,
+    /// Growth of the interval between reductions, used if `first_reduction` is not 0
+    pub reduction_increment: usize,
+    /// Uses recursive (self-subsuming) learnt clause minimization
+    pub cls_min_rec: bool,
+    /// Max length of binary implication chains used in learnt clause minimization
+    pub bin_minimize_depth: usize,
+    /// Reuses the ids of learnt clauses removed by reduction. If false (default), ids are
+    /// monotonic but removed clauses stay as empty slots; so the clause vector grows
+    /// with the total number of learnt clauses. Use `ClauseRef` to hold clauses over
+    /// reductions if true.
+    pub recycle_clause_ids: bool,
+    /// Learnt clauses longer than this are used as reasons of their assertions but
+    /// removed at the next reduction regardless of their rank.
+    pub max_learnt_size: Option<usize>,
 
     //
     //## eliminator
@@ -76,13 +239,45 @@ pub struct Config {
     /// Max #cls for var elimination
     pub elm_var_occ: usize,
 
+    /// Max product of #pos and #neg occurrences to enqueue a var
+    pub elm_occ_lim: usize,
+
+    /// Max average #lit of resolvents in var elimination
+    pub elm_cmb_lim: f64,
+
+    //
+    //## prober
+    //
+    /// Max #propagations in a hyper-binary resolution pass
+    pub hbr_prop_lim: usize,
+
+    /// Max #resolutions in a hyper-ternary resolution pass
+    pub htr_res_lim: usize,
+
     //
     //## vivifier
     //
+    pub enable_vivification: bool,
+
+    //
+    //## restart
+    //
+    /// Blocks restart if the trail is longer than this factor times its average;
+    /// 0.0, the default, disables blocking (Glucose uses 1.4)
+    pub restart_blocking_factor: f64,
 
     //
     //## var rewarding
     //
+    /// Reward vars in the unreachable core whenever a new best assignment is found
+    pub core_bump: bool,
+    /// Polarity policy at rephasing
+    pub rephase_policy: RephasePolicy,
+    /// Polarity of vars before the first search
+    pub initial_polarity: InitialPolarity,
+    /// Inverts the saved phases and forgets the best phases after this many conflicts
+    /// without a new best assignment; `None`, the default, disables it
+    pub stagnation_limit: Option<usize>,
     /// Var Reward Decay Rate
     pub vrw_dcy_rat: f64,
     /// Decay increment step.
@@ -92,49 +287,118 @@ pub struct Config {
 impl Default for Config {
     fn default() -> Self {
         Config {
-            c_cbt_thr: 100,
+            chrono_bt_threshold: Some(100),
             c_cls_lim: 0,
             c_timeout: 5000.0,
+            max_conflicts: None,
+            max_propagations: None,
+            dedup_clauses: false,
+            strict_var_range: true,
 
             splr_interface: false,
             cnf_file: PathBuf::new(),
+            cnf_text: None,
             io_odir: PathBuf::from("."),
             io_pfile: PathBuf::from(CERTIFICATION_DEFAULT_FILENAME),
             io_rfile: PathBuf::new(),
+            dump_on_timeout: None,
+            model_order: ModelOrder::VarIndex,
             no_color: false,
             quiet_mode: false,
             show_journal: false,
             use_certification: false,
+            proof_in_memory: false,
+            proof_emit_deletions: true,
             use_log: false,
+            record_trace: false,
+            self_check: false,
 
             crw_dcy_rat: 0.95,
+            crw_anti_dcy_rat: 0.0,
+            crw_rwd_inc: 1.0,
             cls_rdc_lbd: 5,
             cls_rdc_rm1: 0.2,
             cls_rdc_rm2: 0.05,
+            first_reduction: 0,
+            reduction_increment: 300,
+            cls_min_rec: true,
+            bin_minimize_depth: 1,
+            recycle_clause_ids: false,
+            max_learnt_size: None,
 
             enable_eliminator: !cfg!(feature = "no_clause_elimination"),
             elm_cls_lim: 64,
             elm_grw_lim: 0,
             elm_var_occ: 20000,
+            elm_occ_lim: 800,
+            elm_cmb_lim: 32.0,
+
+            hbr_prop_lim: 100_000,
+            htr_res_lim: 100_000,
+
+            enable_vivification: cfg!(feature = "clause_vivification"),
+
+            restart_blocking_factor: 0.0,
+
+            core_bump: false,
+            rephase_policy: RephasePolicy::BestPhase,
+            initial_polarity: InitialPolarity::OccurrenceBias,
+            stagnation_limit: None,
 
             #[cfg(feature = "EVSIDS")]
             vrw_dcy_rat: 0.98,
-            #[cfg(feature = "LRB_rewarding")]
+            #[cfg(any(feature = "LRB_rewarding", not(feature = "EVSIDS")))]
             vrw_dcy_rat: 0.96,
             #[cfg(feature = "EVSIDS")]
             vrw_dcy_stp: 0.0001,
-            #[cfg(feature = "LRB_rewarding")]
+            #[cfg(any(feature = "LRB_rewarding", not(feature = "EVSIDS")))]
             vrw_dcy_stp: 0.0,
         }
     }
 }
 
 impl Config {
+    /// make a configuration to read a CNF from DIMACS text instead of a file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    /// use crate::splr::types::CNFIndicator;
+    ///
+    /// let config = Config::from_cnf_str("p cnf 2 2\n1 2 0\n-1 0\n");
+    /// assert!(matches!(config.cnf_indicator(), CNFIndicator::LitVec(2)));
+    /// let mut s = Solver::build(&config).expect("panic");
+    /// assert_eq!(s.solve(), Ok(Certificate::SAT(vec![-1, 2])));
+    /// ```
+    pub fn from_cnf_str(cnf: &str) -> Config {
+        Config {
+            cnf_text: Some(cnf.to_string()),
+            ..Config::default()
+        }
+    }
+    /// return where the CNF comes from: `cnf_text` if given, or `cnf_file`.
+    /// The number of clauses in `LitVec` is taken from the header of `cnf_text`.
+    pub fn cnf_indicator(&self) -> CNFIndicator {
+        if let Some(ref text) = self.cnf_text {
+            let num_of_clauses = text
+                .lines()
+                .map(|l| l.split_whitespace().collect::<Vec<_>>())
+                .find(|v| v.len() == 4 && v[0] == "p" && v[1] == "cnf")
+                .and_then(|v| v[3].parse::<usize>().ok())
+                .unwrap_or(0);
+            return CNFIndicator::LitVec(num_of_clauses);
+        }
+        match self.cnf_file.file_name() {
+            Some(f) => CNFIndicator::File(f.to_string_lossy().into_owned()),
+            None => CNFIndicator::Void,
+        }
+    }
     pub fn inject_from_args(&mut self) {
         let mut help = false;
         let mut version = false;
         if 1 < std::env::args().count() {
-            if let Some(ref cnf) = std::env::args().last() {
+            if let Some(ref cnf) = std::env::args().next_back() {
                 // we'll check the existence after parsing all args.
                 self.cnf_file = PathBuf::from(cnf.clone());
             }
@@ -144,20 +408,36 @@ impl Config {
         while let Some(arg) = iter.next() {
             if let Some(stripped) = arg.strip_prefix("--") {
                 let flags = [
-                    "no-color", "quiet", "certify", "journal", "log", "help", "version",
+                    "core-bump",
+                    "input-order",
+                    "no-color",
+                    "no-rec-min",
+                    "quiet",
+                    "certify",
+                    "no-proof-del",
+                    "journal",
+                    "log",
+                    "help",
+                    "version",
                 ];
-                let options_usize = ["cl", "crl", "stat", "ecl", "evl", "evo"];
-                let options_f64 = ["timeout", "cdr", "cr1", "cr2", "vdr", "vds"];
-                let options_path = ["dir", "proof", "result"];
+                let options_usize = ["cl", "crl", "stat", "ecl", "eol", "evl", "evo"];
+                let options_f64 = [
+                    "timeout", "cad", "cdr", "cri", "cr1", "cr2", "ecb", "rbf", "vdr", "vds",
+                ];
+                let options_path = ["dir", "dump", "proof", "result"];
                 let seg: Vec<&str> = stripped.split('=').collect();
                 match seg.len() {
                     1 => {
                         let name = &arg[2..];
                         if flags.contains(&name) {
                             match name {
+                                "core-bump" => self.core_bump = true,
+                                "input-order" => self.model_order = ModelOrder::InputOrder,
                                 "no-color" => self.no_color = true,
+                                "no-rec-min" => self.cls_min_rec = false,
                                 "quiet" => self.quiet_mode = true,
                                 "certify" => self.use_certification = true,
+                                "no-proof-del" => self.proof_emit_deletions = false,
                                 "journal" => self.show_journal = true,
                                 "log" => self.use_log = true,
                                 "help" => help = true,
@@ -171,6 +451,7 @@ impl Config {
                                         "cl" => self.c_cls_lim = val,
                                         "crl" => self.cls_rdc_lbd = val as u16,
                                         "ecl" => self.elm_cls_lim = val,
+                                        "eol" => self.elm_occ_lim = val,
                                         "evl" => self.elm_grw_lim = val,
                                         "evo" => self.elm_var_occ = val,
                                         _ => panic!("invalid option: {name}"),
@@ -186,9 +467,13 @@ impl Config {
                                 if let Ok(val) = str.parse::<f64>() {
                                     match name {
                                         "timeout" => self.c_timeout = val,
+                                        "cad" => self.crw_anti_dcy_rat = val,
                                         "cdr" => self.crw_dcy_rat = val,
+                                        "cri" => self.crw_rwd_inc = val,
                                         "cr1" => self.cls_rdc_rm1 = val,
                                         "cr2" => self.cls_rdc_rm2 = val,
+                                        "ecb" => self.elm_cmb_lim = val,
+                                        "rbf" => self.restart_blocking_factor = val,
                                         "vdr" => self.vrw_dcy_rat = val,
                                         "vds" => self.vrw_dcy_stp = val,
 
@@ -204,6 +489,7 @@ impl Config {
                             if let Some(val) = iter.next() {
                                 match name {
                                     "dir" => self.io_odir = PathBuf::from(val),
+                                    "dump" => self.dump_on_timeout = Some(PathBuf::from(val)),
                                     "proof" => self.io_pfile = PathBuf::from(val),
                                     "result" => self.io_rfile = PathBuf::from(val),
                                     _ => panic!("invalid option: {name}"),
@@ -276,7 +562,7 @@ impl Config {
                 "incremental solver",
                 #[cfg(feature = "just_used")]
                 "use 'just used' flag",
-                #[cfg(feature = "LRB_rewarding")]
+                #[cfg(any(feature = "LRB_rewarding", not(feature = "EVSIDS")))]
                 "Learning-Rate Based rewarding",
                 #[cfg(feature = "reason_side_rewarding")]
                 "reason-side rewarding",
@@ -331,31 +617,49 @@ USAGE:
   splr [FLAGS] [OPTIONS] <cnf-file>
 FLAGS:
   -h, --help                Prints help information
+      --core-bump           Rewards vars in the unreachable core
+      --input-order         Orders a model by the first appearance of vars
   -C, --no-color            Disable coloring
+      --no-rec-min          Disable recursive learnt clause minimization
   -q, --quiet               Disable any progress message
   -c, --certify             Writes a DRAT UNSAT certification file
+      --no-proof-del        Omits clause deletions from the certification
   -j, --journal             Shows log about restart stages
   -l, --log                 Uses Glucose-like progress report
   -V, --version             Prints version information
 OPTIONS:
       --cl <c-cls-lim>      Soft limit of #clauses (6MC/GB){:>10}
-{}{}{}{}      --ecl <elm-cls-lim>   Max #lit for clause subsume    {:>10}
+{}{}{}{}{}{}      --ecb <elm-cmb-lim>   Max avg. #lit of resolvents    {:>10}
+      --ecl <elm-cls-lim>   Max #lit for clause subsume    {:>10}
+      --eol <elm-occ-lim>   Max #pos*#neg to enqueue a var {:>10}
       --evl <elm-grw-lim>   Grow limit of #cls in var elim.{:>10}
       --evo <elm-var-occ>   Max #cls for var elimination   {:>10}
   -o, --dir <io-outdir>     Output directory                {:>10}
+      --dump <dump-file>    Residual CNF filename on timeout
   -p, --proof <io-pfile>    DRAT Cert. filename                 {:>10}
   -r, --result <io-rfile>   Result filename/stdout              {:>10}
+      --rbf <rst-blk-fct>   Restart blocking factor (0: off)  {:>10.2}
   -t, --timeout <timeout>   CPU time limit in sec.         {:>10}
       --vdr <vrw-dcy-rat>   Var reward decay rate             {:>10.2}
 {}ARGS:
   <cnf-file>    DIMACS CNF file
 ",
         config.c_cls_lim,
+        OPTION!(
+            "clause_rewarding",
+            config.crw_anti_dcy_rat,
+            "      --cad <crw-ant-dcy>   Clause reward anti-decay (0: auto){:>10.2}\n"
+        ),
         OPTION!(
             "clause_rewarding",
             config.crw_dcy_rat,
             "      --cdr <crw-dcy-rat>   Clause reward decay rate          {:>10.2}\n"
         ),
+        OPTION!(
+            "clause_rewarding",
+            config.crw_rwd_inc,
+            "      --cri <crw-rwd-inc>   Clause reward increment           {:>10.2}\n"
+        ),
         OPTION!(
             "two_mode_reduction",
             config.cls_rdc_lbd,
@@ -371,12 +675,15 @@ OPTIONS:
             config.cls_rdc_rm2,
             "      --cr2 <cls-rdc-rm2>   Clause reduction ratio for mode2  {:>10.2}\n"
         ),
+        config.elm_cmb_lim,
         config.elm_cls_lim,
+        config.elm_occ_lim,
         config.elm_grw_lim,
         config.elm_var_occ,
         config.io_odir.to_string_lossy(),
         config.io_pfile.to_string_lossy(),
         config.io_rfile.to_string_lossy(),
+        config.restart_blocking_factor,
         config.c_timeout,
         config.vrw_dcy_rat,
         OPTION!(
@@ -387,6 +694,18 @@ OPTIONS:
     )
 }
 
+/// Make a configuration to read a CNF file. A string is always treated as a path;
+/// use [`Config::from_cnf_str`] for DIMACS text.
+///
+/// # Example
+///
+/// ```
+/// use crate::splr::*;
+/// use crate::splr::types::CNFIndicator;
+///
+/// let config = Config::from("cnfs/sample.cnf");
+/// assert!(matches!(config.cnf_indicator(), CNFIndicator::File(f) if f == "sample.cnf"));
+/// ```
 impl<T> From<T> for Config
 where
     PathBuf: From<T>,
@@ -419,6 +738,78 @@ impl Config {
     pub fn override_args(mut self) -> Config {
         self
     }
+    /// build a configuration from the default one overlaid with environment variables.
+    /// Unset variables are ignored. A boolean variable takes `1`, `0`, `true` or `false`.
+    ///
+    /// | variable          | field               | CLI option  |
+    /// |-------------------|---------------------|-------------|
+    /// | `SPLR_TIMEOUT`    | `c_timeout`         | `--timeout` |
+    /// | `SPLR_QUIET`      | `quiet_mode`        | `--quiet`   |
+    /// | `SPLR_ELIMINATOR` | `enable_eliminator` |             |
+    /// | `SPLR_CL`         | `c_cls_lim`         | `--cl`      |
+    /// | `SPLR_CRL`        | `cls_rdc_lbd`       | `--crl`     |
+    /// | `SPLR_CR1`        | `cls_rdc_rm1`       | `--cr1`     |
+    /// | `SPLR_CR2`        | `cls_rdc_rm2`       | `--cr2`     |
+    ///
+    /// # Errors
+    ///
+    /// a message on the first variable with an unparseable value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use splr::Config;
+    ///
+    /// std::env::set_var("SPLR_TIMEOUT", "100");
+    /// std::env::set_var("SPLR_QUIET", "true");
+    /// let config = Config::from_env().expect("panic");
+    /// assert_eq!(config.c_timeout, 100.0);
+    /// assert!(config.quiet_mode);
+    /// std::env::set_var("SPLR_CRL", "-1");
+    /// assert!(Config::from_env().is_err());
+    /// ```
+    pub fn from_env() -> Result<Config, String> {
+        fn var<T: std::str::FromStr>(name: &str) -> Result<Option<T>, String> {
+            match std::env::var(name) {
+                Ok(val) => val
+                    .parse::<T>()
+                    .map(Some)
+                    .map_err(|_| format!("{name}: invalid value {val}")),
+                Err(_) => Ok(None),
+            }
+        }
+        fn flag(name: &str) -> Result<Option<bool>, String> {
+            match std::env::var(name).as_deref() {
+                Ok("1") | Ok("true") => Ok(Some(true)),
+                Ok("0") | Ok("false") => Ok(Some(false)),
+                Ok(val) => Err(format!("{name}: invalid value {val}")),
+                Err(_) => Ok(None),
+            }
+        }
+        let mut config = Config::default();
+        if let Some(val) = var("SPLR_TIMEOUT")? {
+            config.c_timeout = val;
+        }
+        if let Some(val) = flag("SPLR_QUIET")? {
+            config.quiet_mode = val;
+        }
+        if let Some(val) = flag("SPLR_ELIMINATOR")? {
+            config.enable_eliminator = val;
+        }
+        if let Some(val) = var("SPLR_CL")? {
+            config.c_cls_lim = val;
+        }
+        if let Some(val) = var("SPLR_CRL")? {
+            config.cls_rdc_lbd = val;
+        }
+        if let Some(val) = var("SPLR_CR1")? {
+            config.cls_rdc_rm1 = val;
+        }
+        if let Some(val) = var("SPLR_CR2")? {
+            config.cls_rdc_rm2 = val;
+        }
+        Ok(config)
+    }
 }
 
 pub mod property {
@@ -427,21 +818,21 @@ pub mod property {
 
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
     pub enum Tf64 {
-        #[cfg(feature = "clase_rewarding")]
+        #[cfg(feature = "clause_rewarding")]
         ClauseRewardDecayRate,
         VarRewardDecayRate,
     }
 
-    #[cfg(not(feature = "clase_rewarding"))]
+    #[cfg(not(feature = "clause_rewarding"))]
     pub const F64S: [Tf64; 1] = [Tf64::VarRewardDecayRate];
-    #[cfg(feature = "clase_rewarding")]
+    #[cfg(feature = "clause_rewarding")]
     pub const F64S: [Tf64; 2] = [Tf64::ClauseRewardDecayRate, Tf64::VarRewardDecayRate];
 
     impl PropertyDereference<Tf64, f64> for Config {
         #[inline]
         fn derefer(&self, k: Tf64) -> f64 {
             match k {
-                #[cfg(feature = "clase_rewarding")]
+                #[cfg(feature = "clause_rewarding")]
                 Tf64::ClauseRewardDecayRate => self.crw_dcy_rat,
                 Tf64::VarRewardDecayRate => self.vrw_dcy_rat,
             }
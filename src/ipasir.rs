@@ -0,0 +1,322 @@
+//! The [IPASIR](https://github.com/biotomas/ipasir) C interface.
+//! **Requires 'ipasir' feature**, which activates 'incremental_solver' too.
+//!
+//! Build a shared library by `cargo build --lib --release --features ipasir`.
+//!
+//! Assumptions are handled by [`Solver::solve_under_assumptions`] in place; so clauses
+//! learnt in a call are kept for the later ones. `ipasir_failed` reports the assumptions
+//! found by the final conflict analysis of the last unsatisfiable call, which is a valid,
+//! not necessarily minimal, set of failed assumptions.
+use {
+    crate::{
+        config::Config,
+        solver::{Certificate, SatSolverIF, Solver},
+        types::*,
+    },
+    std::{
+        ffi::{c_char, c_int, c_void},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    },
+};
+
+/// A user-supplied termination callback and its state
+struct Terminate {
+    data: *mut c_void,
+    callback: extern "C" fn(*mut c_void) -> c_int,
+}
+
+// The IPASIR protocol lets the user call a solver from one thread at a time.
+unsafe impl Send for Terminate {}
+
+impl Terminate {
+    /// return `true` if the user wants to stop.
+    fn call(&self) -> bool {
+        (self.callback)(self.data) != 0
+    }
+}
+
+/// The solver object passed to C as `void *`
+struct IpasirSolver {
+    solver: Solver,
+    /// literals of the clause under construction
+    clause: Vec<i32>,
+    /// the failed assumptions of the last call which returned UNSAT
+    failed: Vec<i32>,
+    /// `true` if the added clauses are unsatisfiable without any assumption.
+    inconsistent: bool,
+    /// the flag shared with `Solver::state.interrupt`
+    interrupt: Arc<AtomicBool>,
+}
+
+impl IpasirSolver {
+    fn new() -> Self {
+        let config = Config {
+            quiet_mode: true,
+            ..Config::default()
+        };
+        let mut solver = Solver::instantiate(&config, &CNFDescription::default());
+        let interrupt = Arc::new(AtomicBool::new(false));
+        solver.state.interrupt = Some(interrupt.clone());
+        IpasirSolver {
+            solver,
+            clause: Vec::new(),
+            failed: Vec::new(),
+            inconsistent: false,
+            interrupt,
+        }
+    }
+    /// add vars until `lit` is in range.
+    fn extend_vars(&mut self, lit: i32) {
+        while self.solver.asg.num_vars < lit.unsigned_abs() as usize {
+            self.solver.add_var();
+        }
+    }
+}
+
+/// # Safety
+///
+/// `solver` should be a pointer returned by `ipasir_init` and not released.
+unsafe fn as_solver<'a>(solver: *mut c_void) -> &'a mut IpasirSolver {
+    &mut *(solver as *mut IpasirSolver)
+}
+
+/// return the name and the version of the solver.
+#[no_mangle]
+pub extern "C" fn ipasir_signature() -> *const c_char {
+    concat!("splr-", env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char
+}
+
+/// return a new solver.
+#[no_mangle]
+pub extern "C" fn ipasir_init() -> *mut c_void {
+    Box::into_raw(Box::new(IpasirSolver::new())) as *mut c_void
+}
+
+/// release a solver.
+///
+/// # Safety
+///
+/// `solver` should be a pointer returned by `ipasir_init` and not released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_release(solver: *mut c_void) {
+    drop(Box::from_raw(solver as *mut IpasirSolver));
+}
+
+/// add a literal to the clause under construction, or add the clause if `lit` is zero.
+///
+/// # Safety
+///
+/// `solver` should be a pointer returned by `ipasir_init` and not released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_add(solver: *mut c_void, lit: i32) {
+    let s = as_solver(solver);
+    if lit != 0 {
+        s.extend_vars(lit);
+        s.clause.push(lit);
+        return;
+    }
+    let clause = std::mem::take(&mut s.clause);
+    if !s.inconsistent && s.solver.add_clause(&clause).is_err() {
+        s.inconsistent = true;
+    }
+}
+
+/// assume `lit` in the next `ipasir_solve` call. 0 isn't a literal; so it's ignored.
+///
+/// # Safety
+///
+/// `solver` should be a pointer returned by `ipasir_init` and not released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_assume(solver: *mut c_void, lit: i32) {
+    if lit == 0 {
+        return;
+    }
+    let s = as_solver(solver);
+    s.extend_vars(lit);
+    s.solver.push_assumption(Lit::from(lit));
+}
+
+/// solve under the assumptions, then clear them.
+/// Return 10 for SAT, 20 for UNSAT and 0 for interruption.
+///
+/// # Safety
+///
+/// `solver` should be a pointer returned by `ipasir_init` and not released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_solve(solver: *mut c_void) -> c_int {
+    let s = as_solver(solver);
+    s.solver.state.model = None;
+    s.failed.clear();
+    if s.inconsistent {
+        s.solver.state.assumptions.clear();
+        return 20;
+    }
+    s.interrupt.store(false, Ordering::Relaxed);
+    let result = s.solver.solve_under_assumptions();
+    s.solver.state.assumptions.clear();
+    match result {
+        Ok(Certificate::SAT(_)) => 10,
+        Ok(Certificate::UNSAT) => {
+            s.failed = s
+                .solver
+                .state
+                .conflicts
+                .iter()
+                .map(|l| i32::from(*l))
+                .collect();
+            20
+        }
+        Ok(Certificate::UNKNOWN) | Err(_) => 0,
+    }
+}
+
+/// return `lit` if it's true in the last model, `-lit` if false, or 0 if unknown.
+///
+/// # Safety
+///
+/// `solver` should be a pointer returned by `ipasir_init` and not released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_val(solver: *mut c_void, lit: i32) -> i32 {
+    let s = as_solver(solver);
+    match s.solver.state.model {
+        // The model holds the true literal on each var.
+        Some(ref model) if lit != 0 => model
+            .get(lit.unsigned_abs() as usize - 1)
+            .copied()
+            .unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// return 1 if the assumption `lit` was used to prove UNSAT in the last call.
+///
+/// # Safety
+///
+/// `solver` should be a pointer returned by `ipasir_init` and not released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_failed(solver: *mut c_void, lit: i32) -> c_int {
+    as_solver(solver).failed.contains(&lit) as c_int
+}
+
+/// set a callback which is called at every restart; a non-zero value stops the search.
+///
+/// # Safety
+///
+/// `solver` should be a pointer returned by `ipasir_init` and not released.
+/// `data` should stay valid while the solver is alive.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_set_terminate(
+    solver: *mut c_void,
+    data: *mut c_void,
+    terminate: Option<extern "C" fn(*mut c_void) -> c_int>,
+) {
+    let s = as_solver(solver);
+    let Some(callback) = terminate else {
+        s.solver.state.restart.callback = None;
+        return;
+    };
+    let t = Terminate { data, callback };
+    let interrupt = s.interrupt.clone();
+    s.solver.on_restart(Box::new(move |_, _| {
+        if t.call() {
+            interrupt.store(true, Ordering::Relaxed);
+        }
+    }));
+}
+
+/// set a callback to export learnt clauses. Splr doesn't export them; so it is never called.
+///
+/// # Safety
+///
+/// `solver` should be a pointer returned by `ipasir_init` and not released.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_set_learn(
+    _solver: *mut c_void,
+    _data: *mut c_void,
+    _max_length: c_int,
+    _learn: Option<extern "C" fn(*mut c_void, *mut i32)>,
+) {
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern "C" fn stop(_: *mut c_void) -> c_int {
+        1
+    }
+
+    #[test]
+    fn test_ipasir() {
+        unsafe {
+            let s = ipasir_init();
+            for l in [1, 2, 0, -1, 3, 0] {
+                ipasir_add(s, l);
+            }
+            ipasir_assume(s, -2);
+            assert_eq!(ipasir_solve(s), 10);
+            assert_eq!(ipasir_val(s, 1), 1);
+            assert_eq!(ipasir_val(s, -3), 3);
+            // 0 is ignored; 4 isn't used to refute the others.
+            ipasir_assume(s, 0);
+            ipasir_assume(s, 4);
+            ipasir_assume(s, -2);
+            ipasir_assume(s, -3);
+            assert_eq!(ipasir_solve(s), 20);
+            assert_eq!(ipasir_failed(s, -3), 1);
+            assert_eq!(ipasir_failed(s, -2), 1);
+            assert_eq!(ipasir_failed(s, 4), 0);
+            assert_eq!(ipasir_failed(s, 3), 0);
+            // assumptions are cleared after each call.
+            for l in [-2, 0, -3, 0] {
+                ipasir_add(s, l);
+            }
+            ipasir_set_terminate(s, std::ptr::null_mut(), Some(stop));
+            assert_eq!(ipasir_solve(s), 20);
+            assert_eq!(ipasir_failed(s, -3), 0);
+            ipasir_release(s);
+        }
+    }
+
+    #[test]
+    fn test_ipasir_terminate() {
+        // 9 pigeons can't be in 8 holes unless selector 73 lets pigeon 8 fly away.
+        let var = |p: i32, h: i32| 8 * p + h + 1;
+        unsafe {
+            let s = ipasir_init();
+            for p in 0..9 {
+                for h in 0..8 {
+                    ipasir_add(s, var(p, h));
+                }
+                if p == 8 {
+                    ipasir_add(s, 73);
+                }
+                ipasir_add(s, 0);
+            }
+            for h in 0..8 {
+                for p in 0..9 {
+                    for q in p + 1..9 {
+                        ipasir_add(s, -var(p, h));
+                        ipasir_add(s, -var(q, h));
+                        ipasir_add(s, 0);
+                    }
+                }
+            }
+            ipasir_set_terminate(s, std::ptr::null_mut(), Some(stop));
+            ipasir_assume(s, -73);
+            assert_eq!(ipasir_solve(s), 0);
+            assert_eq!(ipasir_failed(s, -73), 0);
+            let num_conflict = as_solver(s).solver.asg.num_conflict;
+            assert!(0 < num_conflict);
+            ipasir_set_terminate(s, std::ptr::null_mut(), None);
+            ipasir_assume(s, 73);
+            assert_eq!(ipasir_solve(s), 10);
+            assert_eq!(ipasir_val(s, 73), 73);
+            assert!(num_conflict <= as_solver(s).solver.asg.num_conflict);
+            ipasir_release(s);
+        }
+    }
+}
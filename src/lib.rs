@@ -42,6 +42,7 @@ let v: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 3], vec![1, -3], vec![-1, 2]];
 match Certificate::try_from(v).expect("panic!") {
     Certificate::UNSAT => 0,
     Certificate::SAT(vec) => vec.len(),
+    Certificate::UNKNOWN => 0,
 };
 ```
 
@@ -56,9 +57,13 @@ pub mod assign;
 /// Module `cdb` provides [`Clause`](`crate::cdb::Clause`) object and its manager [`ClauseDB`](`crate::cdb::ClauseDB`).
 pub mod cdb;
 /// Module `cnf` provides basic operations on CNF files
+#[cfg(not(feature = "no_IO"))]
 pub mod cnf;
 /// Module `config` provides solver's configuration and CLI.
 pub mod config;
+/// Module `ipasir` provides the IPASIR C interface.
+#[cfg(feature = "ipasir")]
+pub mod ipasir;
 /// Module `primitive` provides some fundamental data structures.
 pub mod primitive;
 /// Module `processor` implements a simplifier: clause subsumption and var elimination.
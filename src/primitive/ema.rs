@@ -8,6 +8,7 @@ pub trait EmaIF {
     fn get(&self) -> f64 {
         self.get_fast()
     }
+    /// return the value with the long window.
     fn get_slow(&self) -> f64 {
         unimplemented!()
     }
@@ -59,6 +60,27 @@ impl EmaIF for EmaView {
 }
 
 /// Exponential Moving Average, with a calibrator if feature `EMA_calibration` is on.
+/// `update(x)` moves the value toward `x` by `1 / window`, and `get()` returns the value.
+///
+/// # Example
+///
+/// ```
+/// use splr::primitive::{Ema, EmaIF, EmaMutIF};
+///
+/// let mut ema = Ema::new(10).with_value(0.0);
+/// for _ in 0..100 {
+///     ema.update(1.0);
+/// }
+/// assert!(0.99 < ema.get() && ema.get() <= 1.0);
+/// // a narrower window follows a change faster.
+/// let mut fast = Ema::new(10).with_value(1.0);
+/// ema.set_window(100);
+/// for _ in 0..10 {
+///     ema.update(0.0);
+///     fast.update(0.0);
+/// }
+/// assert!(fast.get() < ema.get());
+/// ```
 #[derive(Clone, Debug)]
 pub struct Ema {
     val: EmaView,
@@ -122,9 +144,33 @@ impl Ema {
         self.val.slow = x;
         self
     }
+    /// change the window size while keeping the current value.
+    pub fn set_window(&mut self, s: usize) {
+        self.sca = 1.0 / (s as f64);
+    }
 }
 
 /// Exponential Moving Average pair, with a calibrator if feature `EMA_calibration` is on.
+/// `get()` (or `get_fast()`) and `get_slow()` return the values with a short and a long window
+/// respectively, and `trend()` returns their ratio.
+///
+/// # Example
+///
+/// ```
+/// use splr::primitive::{Ema2, EmaIF, EmaMutIF};
+///
+/// let mut ema = Ema2::new(10).with_slow(1000).with_value(1.0);
+/// for _ in 0..50 {
+///     ema.update(2.0);
+/// }
+/// assert!(1.99 < ema.get() && ema.get_slow() < 1.1);
+/// assert!(1.0 < ema.trend());
+/// ema.set_window(10, 10);
+/// for _ in 0..200 {
+///     ema.update(2.0);
+/// }
+/// assert!((ema.trend() - 1.0).abs() < 0.01);
+/// ```
 #[derive(Clone, Debug)]
 pub struct Ema2 {
     ema: EmaView,
@@ -226,6 +272,11 @@ impl Ema2 {
     pub fn get_slow(&self) -> f64 {
         self.ema.slow // / self.calf
     }
+    /// change the window sizes while keeping the current values.
+    pub fn set_window(&mut self, fast: usize, slow: usize) {
+        self.fe = 1.0 / (fast as f64);
+        self.se = 1.0 / (slow as f64);
+    }
     /// set value.
     pub fn with_value(mut self, x: f64) -> Self {
         self.ema.fast = x;
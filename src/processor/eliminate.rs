@@ -4,9 +4,6 @@ use {
     crate::{assign::AssignIF, cdb::ClauseDBIF, solver::SolverEvent, state::State, types::*},
 };
 
-// Stop elimination if a generated resolvent is larger than this
-const COMBINATION_LIMIT: f64 = 32.0;
-
 pub fn eliminate_var(
     asg: &mut impl AssignIF,
     cdb: &mut impl ClauseDBIF,
@@ -17,7 +14,7 @@ pub fn eliminate_var(
 ) -> MaybeInconsistent {
     let v = &mut asg.var(vi);
     let w = &mut elim.var[vi];
-    if asg.assign(vi).is_some() || w.aborted {
+    if asg.assign(vi).is_some() || w.aborted || v.is(FlagVar::FROZEN) {
         return Ok(());
     }
     debug_assert!(!v.is(FlagVar::ELIMINATED));
@@ -40,6 +37,7 @@ pub fn eliminate_var(
             &w.neg_occurs,
             vi,
             elim.eliminate_grow_limit,
+            elim.eliminate_combination_limit,
         )
     {
         return Ok(());
@@ -155,6 +153,7 @@ fn skip_var_elimination(
     neg: &[ClauseId],
     v: VarId,
     grow_limit: usize,
+    combination_limit: f64,
 ) -> bool {
     // avoid thrashing
     let limit = match cdb.check_size() {
@@ -175,7 +174,7 @@ fn skip_var_elimination(
                 cnt += 1;
                 average_len *= 1.0 - scale;
                 average_len += scale * clause_size as f64;
-                if clslen + limit < cnt || COMBINATION_LIMIT < average_len {
+                if clslen + limit < cnt || combination_limit < average_len {
                     return true;
                 }
             } else {
@@ -329,6 +328,7 @@ fn make_eliminated_clause(
     println!("# make_eliminated_clause: eliminate({}) clause {}", vi, c);
 }
 
+#[cfg(not(feature = "no_IO"))]
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -356,7 +356,6 @@ mod tests {
                 .collect::<Vec<_>>()
         }
     }
-    #[cfg(not(feature = "no_IO"))]
     #[test]
     fn test_eliminate_var() {
         let Solver {
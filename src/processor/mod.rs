@@ -41,7 +41,7 @@ use {
 /// use crate::{splr::config::Config, splr::types::*};
 /// use crate::splr::processor::{Eliminator, EliminateIF};
 /// use crate::splr::solver::Solver;
-
+///
 /// let mut s = Solver::instantiate(&Config::default(), &CNFDescription::default());
 /// let mut elim = Eliminator::instantiate(&s.state.config, &s.state.cnf);
 /// assert_eq!(elim.is_running(), false);
@@ -57,6 +57,7 @@ pub trait EliminateIF: Instantiate {
     /// simplify database by:
     /// * removing satisfiable clauses
     /// * calling exhaustive simplifier that tries **clause subsumption** and **variable elimination**.
+    ///
     /// Note: `force_run` is used only at the beginning of `solve' for simple satisfiability check
     ///
     /// # Errors
@@ -99,6 +100,8 @@ pub struct Eliminator {
     eliminate_grow_limit: usize,
     /// A criteria by the product's of its positive occurrences and negative ones
     eliminate_occurrence_limit: usize,
+    /// Stop elimination if a generated resolvent is larger than this
+    eliminate_combination_limit: f64,
     /// Stop subsumption if the size of a clause is over this
     subsume_literal_limit: usize,
     /// var
@@ -110,7 +113,11 @@ pub struct Eliminator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{assign::VarManipulateIF, processor::EliminateIF, solver::Solver};
+    use crate::{
+        assign::VarManipulateIF,
+        processor::EliminateIF,
+        solver::{Certificate, SolveIF, Solver},
+    };
     use std::path::Path;
 
     #[test]
@@ -152,4 +159,36 @@ mod tests {
                 .count()
         );
     }
+
+    #[test]
+    fn test_pure_literal_elimination() {
+        let mut s = Solver::try_from(Path::new("cnfs/uf100-010.cnf")).expect("failed to load");
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref mut state,
+            ..
+        } = s;
+        let config = Config {
+            enable_eliminator: true,
+            ..state.config.clone()
+        };
+        let mut elim = Eliminator::instantiate(&config, &state.cnf);
+        elim.eliminate_pure_literals(asg, cdb).expect("panic");
+        // no unassigned var occurs in only one polarity.
+        for vi in 1..=asg.num_vars {
+            if asg.assign(vi).is_some() {
+                continue;
+            }
+            let mut polarities = cdb
+                .iter()
+                .skip(1)
+                .filter(|c| !c.is_dead())
+                .flat_map(|c| c.iter().filter(|l| l.vi() == vi).map(|l| bool::from(*l)));
+            if let Some(b) = polarities.next() {
+                assert!(polarities.any(|p| p != b));
+            }
+        }
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    }
 }
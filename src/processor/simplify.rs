@@ -28,6 +28,7 @@ impl Default for Eliminator {
             eliminate_var_occurrence_limit: 1_000,
             eliminate_grow_limit: 0, // 64
             eliminate_occurrence_limit: 800,
+            eliminate_combination_limit: 32.0,
             subsume_literal_limit: 100,
             var: Vec::new(),
             num_subsumed: 0,
@@ -193,6 +194,8 @@ impl Instantiate for Eliminator {
             var_queue: VarOccHeap::new(nv, 0),
             eliminate_var_occurrence_limit: config.elm_var_occ,
             eliminate_grow_limit: config.elm_grw_lim,
+            eliminate_occurrence_limit: config.elm_occ_lim,
+            eliminate_combination_limit: config.elm_cmb_lim,
             subsume_literal_limit: config.elm_cls_lim,
             var: LitOccurs::new(nv + 1),
             ..Eliminator::default()
@@ -252,7 +255,11 @@ impl EliminateIF for Eliminator {
             return;
         }
         let w = &mut self[vi];
-        if !asg.var(vi).is(FlagVar::ENQUEUED) && w.activity() < self.eliminate_occurrence_limit {
+        let v = asg.var(vi);
+        if !v.is(FlagVar::ENQUEUED)
+            && !v.is(FlagVar::FROZEN)
+            && w.activity() < self.eliminate_occurrence_limit
+        {
             asg.var_mut(vi).turn_on(FlagVar::ENQUEUED);
             self.var_queue.insert(&self.var, vi, upward);
         }
@@ -309,7 +316,8 @@ impl EliminateIF for Eliminator {
     }
     fn stats(&self, vi: VarId) -> Option<(usize, usize)> {
         let w = &self[vi];
-        if w.aborted {
+        // no occurrence lists were built
+        if !self.enable || w.aborted {
             None
         } else {
             Some((w.pos_occurs.len(), w.neg_occurs.len()))
@@ -410,6 +418,104 @@ impl Eliminator {
         }
         self.mode = EliminatorMode::Dormant;
     }
+    /// run clause subsumption on all clauses without var elimination, and return
+    /// the number of subsumed clauses. Occur lists are cleared at the end.
+    ///
+    /// # Errors
+    ///
+    /// if solver becomes inconsistent.
+    pub fn subsume_all(
+        &mut self,
+        asg: &mut impl AssignIF,
+        cdb: &mut impl ClauseDBIF,
+    ) -> Result<usize, SolverError> {
+        debug_assert_eq!(asg.decision_level(), 0);
+        if !self.enable {
+            return Ok(0);
+        }
+        let num_subsumed = self.num_subsumed;
+        if self.mode == EliminatorMode::Dormant {
+            self.prepare(asg, cdb, false);
+        }
+        for ci in 1..cdb.len() {
+            let cid = ClauseId::from(ci);
+            if !cdb[cid].is_dead() {
+                self.enqueue_clause(cid, &mut cdb[cid]);
+            }
+        }
+        let mut timedout: usize = {
+            let nv = asg.derefer(assign::property::Tusize::NumUnassertedVar) as f64;
+            let nc = cdb.derefer(cdb::property::Tusize::NumClause) as f64;
+            (6.0 * nv.log(1.5) * nc) as usize
+        };
+        let result = self
+            .backward_subsumption_check(asg, cdb, &mut timedout)
+            .and_then(|_| {
+                asg.propagate_sandbox(cdb)
+                    .map_err(SolverError::RootLevelConflict)
+            });
+        self.stop(asg, cdb);
+        result.map(|_| self.num_subsumed - num_subsumed)
+    }
+    /// assert pure literals, which occur only positively or only negatively, and remove
+    /// the clauses satisfied by them until no new pure literal appears. Frozen vars and
+    /// vars with too many occurrences are skipped. Return the number of asserted literals.
+    /// Occur lists are cleared at the end.
+    ///
+    /// # Errors
+    ///
+    /// if solver becomes inconsistent.
+    pub fn eliminate_pure_literals(
+        &mut self,
+        asg: &mut impl AssignIF,
+        cdb: &mut impl ClauseDBIF,
+    ) -> Result<usize, SolverError> {
+        debug_assert_eq!(asg.decision_level(), 0);
+        if !self.enable {
+            return Ok(0);
+        }
+        if self.mode == EliminatorMode::Dormant {
+            self.prepare(asg, cdb, false);
+        }
+        let mut num_asserted = 0;
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for vi in 1..=asg.derefer(assign::property::Tusize::NumVar) {
+                let v = asg.var(vi);
+                if v.is(FlagVar::ELIMINATED) || v.is(FlagVar::FROZEN) || asg.assign(vi).is_some() {
+                    continue;
+                }
+                let w = &mut self[vi];
+                if w.aborted {
+                    continue;
+                }
+                let (lit, occurs) = match (w.pos_occurs.is_empty(), w.neg_occurs.is_empty()) {
+                    (false, true) => (Lit::from((vi, true)), std::mem::take(&mut w.pos_occurs)),
+                    (true, false) => (Lit::from((vi, false)), std::mem::take(&mut w.neg_occurs)),
+                    _ => continue,
+                };
+                cdb.certificate_add_assertion(lit);
+                if let Err(e) = asg.assign_at_root_level(lit) {
+                    self.stop(asg, cdb);
+                    return Err(e);
+                }
+                for cid in occurs {
+                    if !cdb[cid].is_dead() {
+                        self.remove_cid_occur(asg, cid, &mut cdb[cid]);
+                        cdb.remove_clause(cid);
+                    }
+                }
+                num_asserted += 1;
+                changed = true;
+            }
+        }
+        let result = asg
+            .propagate_sandbox(cdb)
+            .map_err(SolverError::RootLevelConflict);
+        self.stop(asg, cdb);
+        result.map(|_| num_asserted)
+    }
     /// returns false if solver is inconsistent
     /// - calls `clause_queue.pop`
     pub fn backward_subsumption_check(
@@ -594,9 +700,9 @@ impl Eliminator {
         self.enqueue_var(asg, l.vi(), true);
     }
 
-    ///
-    /// clause queue operations
-    ///
+    //
+    // clause queue operations
+    //
 
     /// enqueue a clause into eliminator's clause queue.
     pub fn enqueue_clause(&mut self, cid: ClauseId, c: &mut Clause) {
@@ -621,9 +727,9 @@ impl Eliminator {
         self.clause_queue.len()
     }
 
-    ///
-    /// var queue operations
-    ///
+    //
+    // var queue operations
+    //
 
     /// clear eliminator's var queue
     fn clear_var_queue(&mut self, asg: &mut impl AssignIF) {
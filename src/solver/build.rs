@@ -3,16 +3,25 @@ use {
     super::{Certificate, Solver, SolverEvent, SolverResult, State, StateIF},
     crate::{
         assign::{AssignIF, AssignStack, PropagateIF, VarManipulateIF},
-        cdb::{ClauseDB, ClauseDBIF},
+        cdb::{ClauseDB, ClauseDBIF, ClauseRef},
+        state::Stat,
         types::*,
     },
+    std::collections::HashSet,
 };
 
 #[cfg(not(feature = "no_IO"))]
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    path::Path,
+use {
+    super::SolveIF,
+    std::{
+        io::BufRead,
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc, Arc,
+        },
+        thread,
+    },
 };
 
 /// API for SAT solver creation and modification.
@@ -46,10 +55,13 @@ pub trait SatSolverIF: Instantiate {
     /// ```
     fn add_assignment(&mut self, val: i32) -> Result<&mut Solver, SolverError>;
     /// add a literal to Solver.
+    /// A given clause is never removed by reduction of learnt clauses, and a learnt
+    /// binary clause identical to it becomes permanent as well.
     ///
     /// # Errors
     ///
-    /// * `SolverError::Inconsistent` if a given clause is unit and conflicts with existing assignments.
+    /// * `SolverError::EmptyClause` if it's empty or falsified by the root-level assignments.
+    ///   Then the solver is unsatisfiable and `solve` returns `Certificate::UNSAT`.
     /// * `SolverError::InvalidLiteral` if a literal in it is out of range for var index.
     ///
     /// # Example
@@ -93,23 +105,92 @@ pub trait SatSolverIF: Instantiate {
     /// assert_eq!(s.solve(), Ok(Certificate::SAT(vec![1, 2, 3, 4, 5, -6, 7, 8, -9])));
     /// ```
     fn add_var(&mut self) -> VarId;
+    /// protect a var from elimination, like MiniSat's `setFrozen`.
+    /// It's useful if the var will be referred in blocking clauses or external queries.
+    /// This has no effect on a var which was already eliminated.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use crate::splr::assign::{self, VarManipulateIF};
+    /// use std::path::Path;
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/sample.cnf")).expect("can't load");
+    /// for vi in 1..=s.asg.num_vars {
+    ///     s.freeze(vi);
+    /// }
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert_eq!(s.asg.derefer(assign::property::Tusize::NumEliminatedVar), 0);
+    /// ```
+    fn freeze(&mut self, vi: VarId);
+    /// allow the eliminator to eliminate a var frozen by `freeze`.
+    fn thaw(&mut self, vi: VarId);
     #[cfg(not(feature = "no_IO"))]
-    /// make a solver and load a CNF into it.
+    /// make a solver and load a CNF into it, from `Config::cnf_text` if given,
+    /// or `Config::cnf_file`.
     ///
     /// # Errors
     ///
     /// * `SolverError::IOError` if it failed to load a CNF file.
+    /// * `SolverError::ParsingCNF` if the CNF has no valid header.
     /// * `SolverError::Inconsistent` if the CNF is conflicting.
     /// * `SolverError::InvalidLiteral` if any literal used in the CNF is out of range for var index.
     fn build(config: &Config) -> Result<Solver, SolverError>;
-    /// reinitialize a solver for incremental solving. **Requires 'incremental_solver' feature**
+    /// reinitialize a solver for the next `solve` by `SolverEvent::Reinitialize`,
+    /// which is a warm start. **Requires 'incremental_solver' feature** to solve again.
+    ///
+    /// * reset: the trail and assignments above the root level, the last model,
+    ///   and the eliminated state of vars (their clauses are restored).
+    /// * kept: clauses including learnt ones, var activities and phases, EMAs and statistics.
     fn reset(&mut self);
+    /// remove all learnt clauses while keeping the given clauses.
+    /// Learnt clauses used as a reason of a root-level assignment survive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    /// use crate::splr::cdb::{self, ClauseDBIF, ClauseIF};
+    /// use std::path::Path;
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/sample.cnf")).expect("can't load");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// s.reset_learnts();
+    /// assert_eq!(s.cdb.derefer(cdb::property::Tusize::NumLearnt), 0);
+    /// assert_eq!(
+    ///     s.cdb.derefer(cdb::property::Tusize::NumClause),
+    ///     s.cdb.iter().skip(1).filter(|c| !c.is_dead()).count()
+    /// );
+    /// ```
+    fn reset_learnts(&mut self);
     #[cfg(not(feature = "no_IO"))]
-    /// dump an UNSAT certification file
+    /// dump an UNSAT certification file, ending with the empty clause.
+    /// `solve` calls this when it returns UNSAT; so the later calls do nothing.
     fn save_certification(&mut self);
     #[cfg(not(feature = "no_IO"))]
     /// dump the current status as a CNF
     fn dump_cnf(&self, fname: &Path);
+    /// write the residual formula in DIMACS format, which consists of root-level assignments
+    /// and the given clauses simplified by them. Clauses on eliminated vars were already
+    /// removed in an equisatisfiable way. So the output is equisatisfiable to the original.
+    ///
+    /// # Errors
+    ///
+    /// * any `std::io::Error` from the writer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((Config::default(), vec![vec![1, 2], vec![-1, 3, 4], vec![-2, -4]].as_ref()))
+    ///     .expect("panic");
+    /// s.add_assignment(-2).expect("panic");
+    /// let mut buf: Vec<u8> = Vec::new();
+    /// s.dump_simplified(&mut buf).expect("panic");
+    /// assert_eq!(String::from_utf8(buf).unwrap(), "p cnf 4 3\n-2 0\n1 0\n-1 3 4 0\n");
+    /// ```
+    fn dump_simplified<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()>;
 }
 
 impl Instantiate for Solver {
@@ -127,6 +208,9 @@ impl Instantiate for Solver {
     }
 }
 
+/// `Err(Ok(Certificate::UNSAT))` means that the problem was found unsatisfiable while
+/// building. [`Solver::from_clauses`] returns it as `Err(SolverError::EmptyClause)` instead.
+///
 /// Example
 ///```
 /// use crate::splr::*;
@@ -140,6 +224,10 @@ impl Instantiate for Solver {
 ///     Solver::try_from((Config::default(), vec![vec![0_i32]].as_ref())),
 ///     Err(Err(SolverError::InvalidLiteral))
 /// ));
+/// assert!(matches!(
+///     Solver::try_from((Config::default(), vec![vec![1, i32::MIN]].as_ref())),
+///     Err(Err(SolverError::InvalidLiteral))
+/// ));
 ///```
 impl<V> TryFrom<(Config, &[V])> for Solver
 where
@@ -156,6 +244,229 @@ where
     }
 }
 
+impl Solver {
+    /// return a new solver built from a stream of clauses without collecting them.
+    /// Vars are added on demand by [`add_var`](`SatSolverIF::add_var`), and neither
+    /// `Config::strict_var_range` nor `Config::dedup_clauses` applies.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::InvalidLiteral` if a clause contains zero.
+    /// * `SolverError::EmptyClause` if it's found unsatisfiable while building,
+    ///   including the case of a given empty clause; treat it as UNSAT.
+    ///
+    /// # Example
+    ///```
+    /// use crate::splr::*;
+    ///
+    /// let clauses = (1..=4_i32).map(|i| vec![-i, i + 1]).chain([vec![1]]);
+    /// let mut s = Solver::from_clauses(&Config::default(), clauses).expect("panic");
+    /// assert_eq!(s.asg.num_vars, 5);
+    /// assert_eq!(s.solve(), Ok(Certificate::SAT(vec![1, 2, 3, 4, 5])));
+    ///
+    /// let mut s = Solver::from_clauses(&Config::default(), &[vec![1, 2], vec![-2]]).expect("panic");
+    /// assert_eq!(s.solve(), Ok(Certificate::SAT(vec![1, -2])));
+    /// assert!(matches!(
+    ///     Solver::from_clauses(&Config::default(), Vec::<Vec<i32>>::new()),
+    ///     Ok(_)
+    /// ));
+    /// assert!(matches!(
+    ///     Solver::from_clauses(&Config::default(), [vec![1], vec![0]]),
+    ///     Err(SolverError::InvalidLiteral)
+    /// ));
+    /// assert!(matches!(
+    ///     Solver::from_clauses(&Config::default(), [vec![1], Vec::new()]),
+    ///     Err(SolverError::EmptyClause)
+    /// ));
+    /// assert!(matches!(
+    ///     Solver::from_clauses(&Config::default(), [vec![1], vec![-1]]),
+    ///     Err(SolverError::EmptyClause)
+    /// ));
+    ///```
+    pub fn from_clauses<I, V>(config: &Config, clauses: I) -> Result<Solver, SolverError>
+    where
+        I: IntoIterator<Item = V>,
+        V: AsRef<[i32]>,
+    {
+        // There's no header to check vars against, nor the number of clauses to bound
+        // the set for deduplication.
+        let config = Config {
+            strict_var_range: false,
+            dedup_clauses: false,
+            ..config.clone()
+        };
+        Solver::instantiate(&config, &CNFDescription::default()).inject_from_vec(clauses)
+    }
+    /// return a new solver built from clauses under `Config::default()`.
+    /// The errors are the same as [`from_clauses`](`Solver::from_clauses`).
+    ///
+    /// # Example
+    ///```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::from_clauses_default([vec![1, 2], vec![-2]]).expect("panic");
+    /// assert_eq!(s.solve(), Ok(Certificate::SAT(vec![1, -2])));
+    /// assert!(matches!(
+    ///     Solver::from_clauses_default([vec![1], vec![-1]]),
+    ///     Err(SolverError::EmptyClause)
+    /// ));
+    /// assert!(matches!(
+    ///     Solver::from_clauses_default([vec![0]]),
+    ///     Err(SolverError::InvalidLiteral)
+    /// ));
+    ///```
+    pub fn from_clauses_default<I, V>(clauses: I) -> Result<Solver, SolverError>
+    where
+        I: IntoIterator<Item = V>,
+        V: AsRef<[i32]>,
+    {
+        Solver::from_clauses(&Config::default(), clauses)
+    }
+    /// add a clause and return a handle for [`remove_clause`](`Solver::remove_clause`),
+    /// or `None` if it wasn't stored as a new clause: it was satisfied, a tautology,
+    /// a unit (asserted permanently), or a duplicate of an existing binary clause.
+    ///
+    /// # Errors
+    ///
+    /// The same as [`add_clause`](`SatSolverIF::add_clause`).
+    ///
+    /// # Example
+    ///```
+    /// use crate::splr::*;
+    ///
+    /// let clauses = vec![vec![1, 2], vec![-1, 2], vec![2, 3]];
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// let handle = s.add_clause_with_ref(&[-2, 3]).expect("panic").expect("not stored");
+    /// assert_eq!(s.add_clause_with_ref(&[1, -1]), Ok(None));
+    /// assert!(s.remove_clause(handle).is_ok());
+    /// assert_eq!(s.remove_clause(handle), Err(SolverError::InvalidClauseRef));
+    /// assert!(s.add_clause_with_ref(&[-2, -3]).expect("panic").is_some());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(v)) if v[1] == 2 && v[2] == -3));
+    ///```
+    pub fn add_clause_with_ref(&mut self, lits: &[i32]) -> Result<Option<ClauseRef>, SolverError> {
+        if lits
+            .iter()
+            .any(|i| *i == 0 || self.asg.num_vars < i.unsigned_abs() as usize)
+        {
+            return Err(SolverError::InvalidLiteral);
+        }
+        let mut clause = lits.iter().map(|i| Lit::from(*i)).collect::<Vec<Lit>>();
+        match self.add_unchecked_clause(&mut clause) {
+            RefClause::EmptyClause => Err(SolverError::EmptyClause),
+            RefClause::Clause(cid) => Ok(Some(self.cdb.clause_ref(cid))),
+            _ => Ok(None),
+        }
+    }
+    /// add a learnt clause received from another solver, such as a peer in a
+    /// clause-sharing portfolio, with its LBD `lbd`. Unlike
+    /// [`add_clause`](`SatSolverIF::add_clause`), reduction can remove it later.
+    /// It's simplified by the root-level assignments, a unit is asserted, and a binary
+    /// clause already registered isn't added again. Call it at the root level, that is,
+    /// before or between calls of `solve`.
+    /// The clause should be a consequence of the given clauses; otherwise the solver
+    /// may return a wrong answer. It's recorded in a DRAT certification as a lemma,
+    /// which a checker may fail to verify.
+    ///
+    /// # Errors
+    ///
+    /// The same as [`add_clause`](`SatSolverIF::add_clause`).
+    ///
+    /// # Example
+    ///```
+    /// use crate::splr::*;
+    ///
+    /// let clauses = vec![vec![1, 2, 3], vec![1, 2, -3], vec![1, -2, 4], vec![-1, 4], vec![-4, 5, 6]];
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// assert!(s.import_learnt(&[1, 2], 2).is_ok());
+    /// assert!(s.import_learnt(&[4], 1).is_ok());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(v)) if v[3] == 4));
+    ///```
+    pub fn import_learnt(&mut self, lits: &[i32], lbd: u16) -> MaybeInconsistent {
+        if lits
+            .iter()
+            .any(|i| *i == 0 || self.asg.num_vars < i.unsigned_abs() as usize)
+        {
+            return Err(SolverError::InvalidLiteral);
+        }
+        let mut clause = lits.iter().map(|i| Lit::from(*i)).collect::<Vec<Lit>>();
+        match self.add_clause_at_root(&mut clause, Some(lbd)) {
+            RefClause::EmptyClause => Err(SolverError::EmptyClause),
+            _ => Ok(()),
+        }
+    }
+    /// remove a clause added by [`add_clause_with_ref`](`Solver::add_clause_with_ref`).
+    /// Non-binary learnt clauses added after it are discarded as well since they may
+    /// depend on it. If `Config::recycle_clause_ids` is on, all of them are discarded,
+    /// because ids don't tell the order of addition.
+    /// Note: root-level assignments and binary learnt clauses derived from it, and
+    /// simplifications by the eliminator using it, are not retracted.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::InvalidClauseRef` if the clause was removed already, by this function
+    ///   or by the solver itself, e.g. through subsumption or var elimination.
+    pub fn remove_clause(&mut self, cr: ClauseRef) -> Result<(), SolverError> {
+        if !self.cdb.is_valid_ref(&cr) {
+            return Err(SolverError::InvalidClauseRef);
+        }
+        self.asg.cancel_until(self.asg.root_level());
+        self.cdb.remove_clause(cr.id);
+        self.cdb.remove_learnts(&self.asg, Some(cr.id));
+        Ok(())
+    }
+    /// add a clause `C` as `C ∨ ¬s` with a fresh selector var `s`, and return `s`.
+    /// `s` is frozen so that the eliminator keeps the clause. The clause is optional until
+    /// you assert the selector by [`add_assignment`](`SatSolverIF::add_assignment`):
+    /// `s` enables the clause and `¬s` retracts it, both permanently. To enable it for
+    /// some calls only, assume `s` by [`push_assumption`](`Solver::push_assumption`)
+    /// and call [`solve_under_assumptions`](`Solver::solve_under_assumptions`).
+    ///
+    /// # Errors
+    ///
+    /// The same as [`add_clause`](`SatSolverIF::add_clause`), checked before minting `s`.
+    ///
+    /// # Example
+    ///```
+    /// use crate::splr::*;
+    ///
+    /// let clauses = vec![vec![1, 2], vec![-2]];
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// let sel = s.add_retractable_clause(&[-1]).expect("panic");
+    /// assert_eq!(i32::from(sel), 3);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(v)) if v[2] == -3));
+    ///
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// let sel = s.add_retractable_clause(&[-1]).expect("panic");
+    /// s.add_assignment(i32::from(sel)).expect("panic");
+    /// assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    ///
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// let sel = s.add_retractable_clause(&[-1]).expect("panic");
+    /// s.push_assumption(sel);
+    /// assert_eq!(s.solve_under_assumptions(), Ok(Certificate::UNSAT));
+    /// assert_eq!(s.pop_assumption(), Some(sel));
+    /// assert!(matches!(s.solve_under_assumptions(), Ok(Certificate::SAT(_))));
+    ///```
+    pub fn add_retractable_clause(&mut self, lits: &[i32]) -> Result<Lit, SolverError> {
+        if lits
+            .iter()
+            .any(|i| *i == 0 || self.asg.num_vars < i.unsigned_abs() as usize)
+        {
+            return Err(SolverError::InvalidLiteral);
+        }
+        if lits.is_empty() {
+            return Err(SolverError::EmptyClause);
+        }
+        let vi = self.add_var();
+        self.freeze(vi);
+        let selector = Lit::from((vi, true));
+        let mut clause = lits.to_vec();
+        clause.push(-i32::from(selector));
+        self.add_clause(clause)?;
+        Ok(selector)
+    }
+}
+
 #[cfg(not(feature = "no_IO"))]
 impl TryFrom<&Path> for Solver {
     type Error = SolverError;
@@ -174,6 +485,106 @@ impl TryFrom<&Path> for Solver {
     }
 }
 
+#[cfg(not(feature = "no_IO"))]
+impl Solver {
+    /// solve a CNF by solvers with `configs` running in parallel threads, and return
+    /// the first SAT or UNSAT result. The others are cancelled through
+    /// `State::interrupt`. Each thread builds its own `Solver`, so nothing is shared
+    /// among them but the clauses, which are parsed once before spawning, and the flag.
+    /// Since all of them can print, `quiet_mode` should be set in `configs`.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::InvalidLiteral` if a literal in the CNF is out of range for var index.
+    /// * the last error if all solvers failed, or `SolverError::UndescribedError` if `configs` is empty.
+    ///   If no solver failed but some timed out, it returns `Ok(Certificate::UNKNOWN)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::splr::*;
+    /// use std::path::Path;
+    ///
+    /// let reader = types::CNFReader::try_from(Path::new("cnfs/uf8.cnf")).expect("can't load");
+    /// let mut config = Config::default();
+    /// config.quiet_mode = true;
+    /// let mut another = config.clone();
+    /// another.cls_min_rec = false;
+    /// let res = Solver::solve_portfolio(reader, &[config, another]);
+    /// assert!(matches!(res, Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn solve_portfolio(cnf: CNFReader, configs: &[Config]) -> SolverResult {
+        let CNFReader { cnf, mut reader } = cnf;
+        // parse the clauses once; each thread injects them from this shared vector.
+        let mut clauses: Vec<Vec<i32>> = Vec::with_capacity(cnf.num_of_clauses);
+        let mut buf = String::new();
+        loop {
+            buf.clear();
+            match reader.read_line(&mut buf) {
+                Ok(0) => break,
+                Ok(_) if buf.trim_start().starts_with('c') => continue,
+                Ok(_) => {
+                    let mut ends_zero = false;
+                    let mut ints: Vec<i32> = Vec::new();
+                    for s in buf.split_whitespace() {
+                        match s.parse::<i32>() {
+                            Ok(0) => {
+                                ends_zero = true;
+                                break;
+                            }
+                            Ok(val) => ints.push(val),
+                            Err(_) => (),
+                        }
+                    }
+                    if !ints.is_empty() {
+                        clauses.push(ints);
+                    } else if ends_zero {
+                        return Ok(Certificate::UNSAT);
+                    }
+                }
+                Err(_) => return Err(SolverError::IOError),
+            }
+        }
+        let interrupt = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel::<SolverResult>();
+        thread::scope(|scope| {
+            for config in configs.iter() {
+                let tx = tx.clone();
+                let interrupt = interrupt.clone();
+                let cnf = &cnf;
+                let clauses = &clauses;
+                scope.spawn(move || {
+                    let res = match Solver::instantiate(config, cnf).inject_from_vec(clauses) {
+                        Ok(mut s) => {
+                            s.state.interrupt = Some(interrupt.clone());
+                            s.solve()
+                        }
+                        Err(SolverError::EmptyClause | SolverError::RootLevelConflict(_)) => {
+                            Ok(Certificate::UNSAT)
+                        }
+                        Err(e) => Err(e),
+                    };
+                    if matches!(res, Ok(Certificate::SAT(_) | Certificate::UNSAT)) {
+                        interrupt.store(true, Ordering::Relaxed);
+                    }
+                    let _ = tx.send(res);
+                });
+            }
+            drop(tx);
+            let mut result = Err(SolverError::UndescribedError);
+            for res in rx.iter() {
+                match res {
+                    Ok(Certificate::SAT(_) | Certificate::UNSAT) => return res,
+                    Ok(Certificate::UNKNOWN) => result = res,
+                    Err(_) if result.is_err() => result = res,
+                    Err(_) => (),
+                }
+            }
+            result
+        })
+    }
+}
+
 impl SatSolverIF for Solver {
     fn add_assignment(&mut self, val: i32) -> Result<&mut Solver, SolverError> {
         if val == 0 || self.asg.num_vars < val.unsigned_abs() as usize {
@@ -181,6 +592,8 @@ impl SatSolverIF for Solver {
         }
         let lit = Lit::from(val);
         self.cdb.certificate_add_assertion(lit);
+        self.state.record_original_clause(&[lit]);
+        self.state.record_input_order(&[lit]);
         match self.asg.assigned(lit) {
             None => self.asg.assign_at_root_level(lit).map(|_| self),
             Some(true) => Ok(self),
@@ -206,8 +619,10 @@ impl SatSolverIF for Solver {
             .collect::<Vec<Lit>>();
 
         if clause.is_empty() {
+            self.state.inconsistent = true;
             return Err(SolverError::EmptyClause);
         }
+        self.state.record_original_clause(&clause);
         if self.add_unchecked_clause(&mut clause) == RefClause::EmptyClause {
             return Err(SolverError::EmptyClause);
         }
@@ -225,6 +640,12 @@ impl SatSolverIF for Solver {
         state.handle(SolverEvent::NewVar);
         asg.num_vars as VarId
     }
+    fn freeze(&mut self, vi: VarId) {
+        self.asg.var_mut(vi).turn_on(FlagVar::FROZEN);
+    }
+    fn thaw(&mut self, vi: VarId) {
+        self.asg.var_mut(vi).turn_off(FlagVar::FROZEN);
+    }
     /// # Examples
     ///
     /// ```
@@ -236,6 +657,16 @@ impl SatSolverIF for Solver {
     ///```
     #[cfg(not(feature = "no_IO"))]
     fn build(config: &Config) -> Result<Solver, SolverError> {
+        if let Some(ref text) = config.cnf_text {
+            let mut reader = text.as_bytes();
+            let (nv, nc) = read_cnf_header(&mut reader, "--")?;
+            let cnf = CNFDescription {
+                num_of_variables: nv,
+                num_of_clauses: nc,
+                pathname: CNFIndicator::LitVec(nc),
+            };
+            return Solver::instantiate(config, &cnf).inject(reader);
+        }
         let CNFReader { cnf, reader } = CNFReader::try_from(Path::new(&config.cnf_file))?;
         Solver::instantiate(config, &cnf).inject(reader)
     }
@@ -259,6 +690,15 @@ impl SatSolverIF for Solver {
             }
         }
     }
+    fn reset_learnts(&mut self) {
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ..
+        } = self;
+        asg.cancel_until(asg.root_level());
+        cdb.remove_learnts(asg, None);
+    }
     #[cfg(not(feature = "no_IO"))]
     /// dump an UNSAT certification file
     fn save_certification(&mut self) {
@@ -270,19 +710,125 @@ impl SatSolverIF for Solver {
         let Solver { asg, cdb, .. } = self;
         cdb.dump_cnf(asg, fname)
     }
+    fn dump_simplified<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        let Solver { asg, cdb, .. } = self;
+        write_residual(asg, cdb, w, false)
+    }
+}
+
+/// write the residual formula in DIMACS format; see [`dump_simplified`](`SatSolverIF::dump_simplified`).
+/// Learnt clauses are included if `with_learnts` holds.
+pub(super) fn write_residual<W: std::io::Write>(
+    asg: &AssignStack,
+    cdb: &ClauseDB,
+    w: &mut W,
+    with_learnts: bool,
+) -> std::io::Result<()> {
+    let root = |l: &Lit| {
+        let vi = l.vi();
+        if asg.level(vi) == 0 {
+            asg.assigned(*l)
+        } else {
+            None
+        }
+    };
+    // Since the trail is wiped at the root level, units are collected from the assignments.
+    let mut clauses: Vec<Vec<i32>> = (1..=asg.num_vars)
+        .filter(|vi| asg.level(*vi) == 0 && !asg.var(*vi).is(FlagVar::ELIMINATED))
+        .filter_map(|vi| asg.assign(vi).map(|b| vec![i32::from(Lit::from((vi, b)))]))
+        .collect::<Vec<_>>();
+    for (cid, lits) in cdb.iter_active() {
+        if (!with_learnts && cdb[cid].is(FlagClause::LEARNT))
+            || lits.iter().any(|l| root(l) == Some(true))
+        {
+            continue;
+        }
+        clauses.push(
+            lits.iter()
+                .filter(|l| root(l).is_none())
+                .map(|l| i32::from(*l))
+                .collect::<Vec<_>>(),
+        );
+    }
+    let nv = clauses
+        .iter()
+        .flat_map(|c| c.iter())
+        .map(|l| l.unsigned_abs() as usize)
+        .max()
+        .unwrap_or(0);
+    writeln!(w, "p cnf {} {}", nv, clauses.len())?;
+    for c in clauses.iter() {
+        for l in c.iter() {
+            write!(w, "{l} ")?;
+        }
+        writeln!(w, "0")?;
+    }
+    Ok(())
 }
 
+/// The max number of clauses in a CNF checked by `Config::dedup_clauses`.
+/// Larger ones are loaded as they are, since the hash set would be too large.
+const DEDUP_CLAUSE_LIMIT: usize = 4_000_000;
+
 impl Solver {
+    /// return a set to detect duplicate clauses while loading a CNF,
+    /// or `None` if `Config::dedup_clauses` is off or the CNF is too large.
+    fn clause_set_for_dedup(&self) -> Option<HashSet<Vec<Lit>>> {
+        (self.state.config.dedup_clauses && self.state.target.num_of_clauses <= DEDUP_CLAUSE_LIMIT)
+            .then(HashSet::new)
+    }
+    /// return `true` if `lits` is a duplicate of a clause in `set`, then count it.
+    fn is_duplicate(&mut self, set: &mut Option<HashSet<Vec<Lit>>>, lits: &[Lit]) -> bool {
+        let Some(set) = set else {
+            return false;
+        };
+        let mut key = lits.to_vec();
+        key.sort_unstable();
+        key.dedup();
+        if set.insert(key) {
+            return false;
+        }
+        self.state[Stat::DuplicateClause] += 1;
+        true
+    }
+    /// check that `ints` are valid literals whose vars are in range, or add vars to hold
+    /// them if `Config::strict_var_range` is off. It must be called before `Lit::from`.
+    fn fit_var_range(&mut self, ints: &[i32]) -> Result<(), SolverError> {
+        if ints.iter().any(|i| *i == 0 || *i == i32::MIN) {
+            return Err(SolverError::InvalidLiteral);
+        }
+        let max_vi = ints
+            .iter()
+            .map(|i| i.unsigned_abs() as usize)
+            .max()
+            .unwrap_or(0);
+        if max_vi <= self.asg.num_vars {
+            return Ok(());
+        }
+        if self.state.config.strict_var_range {
+            return Err(SolverError::InvalidLiteral);
+        }
+        while self.asg.num_vars < max_vi {
+            self.add_var();
+        }
+        Ok(())
+    }
     // renamed from clause_new
     fn add_unchecked_clause(&mut self, lits: &mut Vec<Lit>) -> RefClause {
+        self.add_clause_at_root(lits, None)
+    }
+    /// add a clause at the root level, as a learnt of rank `learnt` if given.
+    fn add_clause_at_root(&mut self, lits: &mut Vec<Lit>, learnt: Option<u16>) -> RefClause {
         let Solver {
             ref mut asg,
             ref mut cdb,
-            ..
+            ref mut state,
         } = self;
         if lits.is_empty() {
+            state.inconsistent = true;
             return RefClause::EmptyClause;
         }
+        state.record_input_order(lits);
         debug_assert!(asg.decision_level() == 0);
         lits.sort();
         let mut j = 0;
@@ -300,21 +846,29 @@ impl Solver {
         }
         lits.truncate(j);
         match lits.len() {
-            0 => RefClause::EmptyClause, // for UNSAT
+            0 => {
+                // falsified by the root-level assignments: UNSAT
+                state.inconsistent = true;
+                RefClause::EmptyClause
+            }
             1 => {
                 let l0 = lits[0];
                 cdb.certificate_add_assertion(l0);
                 asg.assign_at_root_level(l0)
                     .map_or(RefClause::EmptyClause, |_| RefClause::UnitClause(l0))
             }
-            _ => cdb.new_clause(asg, lits, false),
+            _ => match learnt {
+                Some(rank) => cdb.add_learnt_clause(asg, lits, rank),
+                None => cdb.add_permanent_clause(asg, lits),
+            },
         }
     }
     #[cfg(not(feature = "no_IO"))]
-    fn inject(mut self, mut reader: BufReader<File>) -> Result<Solver, SolverError> {
+    fn inject(mut self, mut reader: impl BufRead) -> Result<Solver, SolverError> {
         self.state.progress_header();
         self.state.progress(&self.asg, &self.cdb);
         self.state.flush("Initialization phase: loading...");
+        let mut clause_set = self.clause_set_for_dedup();
         let mut buf = String::new();
         loop {
             buf.clear();
@@ -324,23 +878,30 @@ impl Solver {
                 Ok(_) if buf.starts_with('c') => continue,
                 Ok(_) => {
                     let iter = buf.split_whitespace();
-                    let mut v: Vec<Lit> = Vec::new();
+                    let mut ints: Vec<i32> = Vec::new();
                     for s in iter {
                         match s.parse::<i32>() {
                             Ok(0) => {
                                 ends_zero = true;
                                 break;
                             }
-                            Ok(val) => v.push(Lit::from(val)),
+                            Ok(val) => ints.push(val),
                             Err(_) => (),
                         }
                     }
-                    if v.is_empty() {
+                    if ints.is_empty() {
                         if ends_zero {
                             return Err(SolverError::EmptyClause);
                         }
                         continue;
-                    } else if self.add_unchecked_clause(&mut v) == RefClause::EmptyClause {
+                    }
+                    self.fit_var_range(&ints)?;
+                    let mut v = ints.iter().map(|i| Lit::from(*i)).collect::<Vec<Lit>>();
+                    if self.is_duplicate(&mut clause_set, &v) {
+                        continue;
+                    }
+                    self.state.record_original_clause(&v);
+                    if self.add_unchecked_clause(&mut v) == RefClause::EmptyClause {
                         return Err(SolverError::EmptyClause);
                     }
                 }
@@ -351,27 +912,29 @@ impl Solver {
         // s.state[Stat::NumBin] = s.cdb.iter().skip(1).filter(|c| c.len() == 2).count();
         Ok(self)
     }
-    fn inject_from_vec<V>(mut self, v: &[V]) -> Result<Solver, SolverError>
+    fn inject_from_vec<I, V>(mut self, v: I) -> Result<Solver, SolverError>
     where
+        I: IntoIterator<Item = V>,
         V: AsRef<[i32]>,
     {
         self.state.progress_header();
         self.state.progress(&self.asg, &self.cdb);
         self.state.flush("injecting...");
-        for ints in v.iter() {
-            for i in ints.as_ref().iter() {
-                if *i == 0 || self.asg.num_vars < i.unsigned_abs() as usize {
-                    return Err(SolverError::InvalidLiteral);
-                }
+        let mut clause_set = self.clause_set_for_dedup();
+        for ints in v {
+            if ints.as_ref().is_empty() {
+                return Err(SolverError::EmptyClause);
             }
+            self.fit_var_range(ints.as_ref())?;
             let mut lits = ints
                 .as_ref()
                 .iter()
                 .map(|i| Lit::from(*i))
                 .collect::<Vec<Lit>>();
-            if v.is_empty() {
-                return Err(SolverError::EmptyClause);
+            if self.is_duplicate(&mut clause_set, &lits) {
+                continue;
             }
+            self.state.record_original_clause(&lits);
             if self.add_unchecked_clause(&mut lits) == RefClause::EmptyClause {
                 return Err(SolverError::EmptyClause);
             }
@@ -382,13 +945,13 @@ impl Solver {
     }
 }
 
+#[cfg(not(feature = "no_IO"))]
 #[cfg(test)]
 mod tests {
     // use super::*;
     use crate::*;
     use std::path::Path;
 
-    #[cfg(not(feature = "no_IO"))]
     #[test]
     fn test_add_var() {
         let mut s = Solver::try_from(Path::new("cnfs/uf8.cnf")).expect("can't load");
@@ -411,4 +974,125 @@ mod tests {
             Ok(Certificate::SAT(vec![1, 2, 3, 4, 5, -6, 7, 8, -9]))
         );
     }
+
+    #[test]
+    fn test_var_beyond_header() {
+        let text = "p cnf 2 3\n1 2 0\n-1 3 0\n-3 -2 0\n";
+        let config = Config::from_cnf_str(text);
+        assert!(matches!(
+            Solver::build(&config),
+            Err(SolverError::InvalidLiteral)
+        ));
+        let config = Config {
+            strict_var_range: false,
+            ..Config::from_cnf_str(text)
+        };
+        let mut s = Solver::build(&config).expect("panic");
+        assert_eq!(s.asg.num_vars, 3);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(m)) if m.len() == 3));
+        let config = Config {
+            strict_var_range: false,
+            ..Config::from_cnf_str("p cnf 2 1\n1 -2147483648 0\n")
+        };
+        assert!(matches!(
+            Solver::build(&config),
+            Err(SolverError::InvalidLiteral)
+        ));
+    }
+
+    #[test]
+    fn test_portfolio_with_empty_clause() {
+        let path = std::env::temp_dir().join(format!("splr-portfolio-{}.cnf", std::process::id()));
+        std::fs::write(&path, "p cnf 2 3\n1 2 0\n0\n-1 2 0\n").expect("can't write");
+        let reader = types::CNFReader::try_from(path.as_path()).expect("can't load");
+        let _ = std::fs::remove_file(&path);
+        let config = Config {
+            quiet_mode: true,
+            ..Config::default()
+        };
+        let res = Solver::solve_portfolio(reader, &[config.clone(), config]);
+        assert_eq!(res, Ok(Certificate::UNSAT));
+    }
+
+    #[test]
+    fn test_dump_simplified_after_simplification() {
+        use crate::assign::PropagateIF;
+        let clauses = vec![
+            vec![1, 2],
+            vec![-1, 3, 4],
+            vec![-2, -4],
+            vec![3, 5],
+            vec![-3, 5],
+        ];
+        let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+        s.add_assignment(-2).expect("panic");
+        assert!(s.preprocess().is_ok());
+        // wipe the root-level trail as search does
+        let Solver { asg, cdb, .. } = &mut s;
+        assert!(asg.clear_asserted_literals(cdb).is_ok());
+        assert_eq!(s.trail_len(), 0);
+        let mut buf: Vec<u8> = Vec::new();
+        s.dump_simplified(&mut buf).expect("panic");
+        let dump = String::from_utf8(buf).unwrap();
+        for l in s.root_assignments() {
+            assert!(dump.lines().any(|c| c == format!("{} 0", i32::from(l))));
+        }
+        assert!(dump.lines().any(|c| c == "-2 0"));
+    }
+
+    #[test]
+    fn test_dedup_clauses() {
+        use crate::{cdb, state::Stat, types::PropertyDereference};
+        let clauses = vec![
+            vec![1, 2, 3],
+            vec![3, 2, 1],
+            vec![-1, 2, 3],
+            vec![2, 1, 3, 1],
+            vec![-3, 4],
+        ];
+        for dedup in [false, true] {
+            let config = Config {
+                dedup_clauses: dedup,
+                ..Config::default()
+            };
+            let s = Solver::try_from((config, clauses.as_ref())).expect("panic");
+            let num_dropped = if dedup { 2 } else { 0 };
+            assert_eq!(s.state[Stat::DuplicateClause], num_dropped);
+            assert_eq!(
+                s.cdb.derefer(cdb::property::Tusize::NumClause),
+                clauses.len() - num_dropped
+            );
+        }
+    }
+
+    #[test]
+    fn test_import_learnt() {
+        use crate::{
+            cdb,
+            types::{FlagClause, FlagIF, PropertyDereference},
+        };
+        let clauses = vec![vec![1, 2, 3, 4], vec![-1, 2], vec![-2, 5, 6, 7]];
+        let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+        assert_eq!(
+            s.import_learnt(&[1, 8], 2),
+            Err(SolverError::InvalidLiteral)
+        );
+        assert!(s.import_learnt(&[3, 5, 6, 7], 9).is_ok());
+        assert!(s.import_learnt(&[2, 3, 4, 5], 2).is_ok());
+        assert_eq!(s.cdb.derefer(cdb::property::Tusize::NumLearnt), 2);
+        assert_eq!(s.cdb.derefer(cdb::property::Tusize::NumLBD2), 1);
+        let ranks = s
+            .cdb
+            .iter_active()
+            .map(|(cid, _)| &s.cdb[cid])
+            .filter(|c| c.is(FlagClause::LEARNT))
+            .map(|c| c.rank)
+            .collect::<Vec<_>>();
+        assert_eq!(ranks, vec![9, 2]);
+        // a unit is asserted, and the clause satisfied by it isn't added.
+        assert!(s.import_learnt(&[2], 1).is_ok());
+        assert!(s.import_learnt(&[2, -3, 6, 7], 3).is_ok());
+        assert_eq!(s.cdb.derefer(cdb::property::Tusize::NumLearnt), 2);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(v)) if v[1] == 2));
+    }
 }
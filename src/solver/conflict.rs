@@ -8,10 +8,24 @@ use {
     crate::{
         assign::{AssignIF, AssignStack, PropagateIF, VarManipulateIF},
         cdb::{ClauseDB, ClauseDBIF},
+        state::Stat,
         types::*,
     },
 };
 
+/// return the level to backtrack to after learning a clause asserted at `assign_level`.
+/// It's chronological only if the backjump distance reaches the threshold.
+fn backtrack_level(
+    threshold: Option<DecisionLevel>,
+    assign_level: DecisionLevel,
+    conflicting_level: DecisionLevel,
+) -> DecisionLevel {
+    match threshold {
+        Some(t) if assign_level + t <= conflicting_level => conflicting_level - 1,
+        _ => assign_level,
+    }
+}
+
 /// returns:
 /// - 0: if a new assigngment is generated by conflict analysis.
 /// - 1: if a binary link generated
@@ -42,9 +56,12 @@ pub fn handle_conflict(
 
     // If we can settle this conflict w/o restart, solver will get a big progress.
     #[cfg(feature = "chrono_BT")]
-    let chronobt = 1000 < asg.num_conflict && 0 < state.config.c_cbt_thr;
+    let chrono_bt_threshold = state
+        .config
+        .chrono_bt_threshold
+        .filter(|_| 1000 < asg.num_conflict);
     #[cfg(not(feature = "chrono_BT"))]
-    let chronobt: bool = false;
+    let chrono_bt_threshold: Option<DecisionLevel> = None;
 
     #[cfg(feature = "chrono_BT")]
     {
@@ -55,8 +72,7 @@ pub fn handle_conflict(
         let level = asg.level_ref();
         let max_level = c.iter().map(|l| level[l.vi()]).max().unwrap();
 
-        if chronobt
-            && state.config.c_cbt_thr < conflicting_level
+        if chrono_bt_threshold.map_or(false, |t| t < conflicting_level)
             && 1 == c.iter().filter(|l| level[l.vi()] == max_level).count()
         {
             if let Some(second_level) = c
@@ -83,15 +99,6 @@ pub fn handle_conflict(
     let new_learnt = &mut state.new_learnt;
     let learnt_len = new_learnt.len();
     if learnt_len == 0 {
-        #[cfg(debug)]
-        {
-            println!(
-                "empty learnt at {}({}) by {:?}",
-                cl,
-                asg.reason(asg.decision_vi(cl)).is_none(),
-                asg.dump(asg, &cdb[ci]),
-            );
-        }
         return Err(SolverError::EmptyClause);
     }
     let l0 = new_learnt[0];
@@ -162,11 +169,11 @@ pub fn handle_conflict(
             AssignReason::None => unreachable!("handle_conflict"),
         }
     }
-    if chronobt && assign_level + state.config.c_cbt_thr <= conflicting_level {
-        asg.cancel_until(conflicting_level - 1);
-    } else {
-        asg.cancel_until(assign_level);
-    }
+    asg.cancel_until(backtrack_level(
+        chrono_bt_threshold,
+        assign_level,
+        conflicting_level,
+    ));
     debug_assert_eq!(asg.assigned(l0), None);
     debug_assert_eq!(
         new_learnt.iter().skip(1).map(|l| asg.level(l.vi())).max(),
@@ -210,6 +217,15 @@ pub fn handle_conflict(
                 assign_level,
             );
             // || check_graph(asg, cdb, l0, "clause");
+            // An oversized clause works as the reason for now but isn't kept.
+            if state
+                .config
+                .max_learnt_size
+                .map_or(false, |limit| limit < learnt_len)
+            {
+                cdb[cid].turn_on(FlagClause::OVERSIZED);
+                state[Stat::OversizedLearnt] += 1;
+            }
             rank = cdb[cid].rank;
             if rank <= 20 {
                 for cid in &state.derive20 {
@@ -407,7 +423,7 @@ fn conflict_analyze(
         //
         // set the index of the next literal to trail_index
         //
-        #[allow(clippy::blocks_in_if_conditions)]
+        #[allow(clippy::blocks_in_conditions)]
         while {
             let vi = asg.stack(trail_index).vi();
             boundary_check!(
@@ -454,13 +470,14 @@ fn conflict_analyze(
         learnt[0],
         learnt
     );
-    minimize_learnt(&mut state.new_learnt, asg, cdb)
+    minimize_learnt(&mut state.new_learnt, asg, cdb, state.config.cls_min_rec)
 }
 
 fn minimize_learnt(
     new_learnt: &mut Vec<Lit>,
     asg: &mut AssignStack,
     cdb: &mut ClauseDB,
+    recursive: bool,
 ) -> DecisionLevel {
     let mut to_clear: Vec<Lit> = vec![new_learnt[0]];
     let mut levels = vec![false; asg.decision_level() as usize + 1];
@@ -470,7 +487,11 @@ fn minimize_learnt(
         levels[level[l.vi()] as usize] = true;
     }
     let l0 = new_learnt[0];
-    new_learnt.retain(|l| *l == l0 || !l.is_redundant(asg, cdb, &mut to_clear, &levels));
+    if recursive {
+        new_learnt.retain(|l| *l == l0 || !l.is_redundant(asg, cdb, &mut to_clear, &levels));
+    } else {
+        new_learnt.retain(|l| *l == l0 || !l.is_locally_redundant(asg, cdb));
+    }
     let len = new_learnt.len();
     if 2 < len && len < 30 {
         cdb.minimize_with_bi_clauses(asg, new_learnt);
@@ -567,6 +588,19 @@ impl Lit {
         }
         true
     }
+    /// return `true` if all literals in the reason of `lit` are already in the learnt
+    /// or asserted. This is the non-recursive (local) version of `is_redundant`.
+    fn is_locally_redundant(self, asg: &AssignStack, cdb: &ClauseDB) -> bool {
+        let in_learnt = |l: &Lit| {
+            let vi = l.vi();
+            asg.level(vi) == 0 || asg.var(vi).is(FlagVar::CA_SEEN)
+        };
+        match asg.reason(self.vi()) {
+            AssignReason::BinaryLink(l) => in_learnt(&l),
+            AssignReason::Implication(cid) => cdb[cid][1..].iter().all(in_learnt),
+            AssignReason::Decision(_) | AssignReason::None => false,
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -683,3 +717,56 @@ fn tracer(asg: &AssignStack, cdb: &ClauseDB) {
     }
     panic!("done");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backtrack_level() {
+        assert_eq!(backtrack_level(Some(10), 2, 20), 19);
+        assert_eq!(backtrack_level(Some(10), 2, 11), 2);
+        for conflicting_level in 1..200 {
+            assert_eq!(backtrack_level(None, 0, conflicting_level), 0);
+            assert_eq!(backtrack_level(None, 3, conflicting_level + 3), 3);
+        }
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_max_learnt_size() {
+        use crate::{
+            config::Config,
+            solver::{Certificate, SatSolverIF, SolveIF, Solver},
+        };
+        let config = Config {
+            max_learnt_size: Some(8),
+            quiet_mode: true,
+            ..Config::from("cnfs/uf100-010.cnf")
+        };
+        let mut s = Solver::build(&config).expect("panic");
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        assert!(0 < s.state[Stat::OversizedLearnt]);
+        // Oversized clauses are still used as reasons; so the search stays complete.
+        // pigeonhole: 6 pigeons into 5 holes
+        let (pigeons, holes) = (6, 5);
+        let var = |p: i32, h: i32| p * holes + h + 1;
+        let mut clauses = (0..pigeons)
+            .map(|p| (0..holes).map(|h| var(p, h)).collect::<Vec<i32>>())
+            .collect::<Vec<_>>();
+        for h in 0..holes {
+            for p in 0..pigeons {
+                for q in p + 1..pigeons {
+                    clauses.push(vec![-var(p, h), -var(q, h)]);
+                }
+            }
+        }
+        let config = Config {
+            max_learnt_size: Some(3),
+            quiet_mode: true,
+            ..Config::default()
+        };
+        let mut s = Solver::try_from((config, clauses.as_ref())).expect("panic");
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        assert!(0 < s.state[Stat::OversizedLearnt]);
+    }
+}
@@ -0,0 +1,674 @@
+//! Inprocessing passes which users can run on demand between `solve` calls
+use {
+    super::{Certificate, SatSolverIF, Solver},
+    crate::{
+        assign::{self, AssignIF, PropagateIF, VarManipulateIF},
+        cdb::{ClauseDBIF, ClauseId},
+        config::Config,
+        processor::{EliminateIF, Eliminator},
+        state::{Stat, State},
+        types::*,
+    },
+    std::collections::HashSet,
+};
+
+impl Solver {
+    /// run clause subsumption and var elimination once without search.
+    /// The solver is left at the root level; so it's ready for
+    /// [`dump_simplified`](`SatSolverIF::dump_simplified`) or [`solve`](`SolveIF::solve`).
+    /// Frozen vars are not eliminated.
+    ///
+    /// # Errors
+    ///
+    /// `SolverError::Inconsistent` if the formula is found unsatisfiable.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from(std::path::Path::new("cnfs/sample.cnf")).expect("panic");
+    /// assert!(s.preprocess().is_ok());
+    /// assert!((1..=s.asg.num_vars).any(|vi| s.is_eliminated(vi)));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    ///
+    /// let mut s = Solver::try_from((
+    ///     Config::default(),
+    ///     vec![vec![1, 2], vec![1, -2], vec![-1, 2], vec![-1, -2]].as_ref(),
+    /// ))
+    /// .expect("panic");
+    /// assert_eq!(s.preprocess(), Err(SolverError::Inconsistent));
+    /// ```
+    pub fn preprocess(&mut self) -> MaybeInconsistent {
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref mut state,
+        } = self;
+        debug_assert_eq!(asg.decision_level(), asg.root_level());
+        let mut elim = eliminator_on_demand(state);
+        match elim.simplify(asg, cdb, state, false) {
+            Ok(()) => (),
+            Err(SolverError::OutOfMemory) => return Err(SolverError::OutOfMemory),
+            Err(_) => return Err(SolverError::Inconsistent),
+        }
+        asg.eliminated.append(elim.eliminated_lits());
+        state[Stat::Simplify] += 1;
+        state[Stat::SubsumedClause] = elim.num_subsumed;
+        Ok(())
+    }
+    /// run clause subsumption only, without var elimination, and return the number of
+    /// removed clauses. Clauses can be strengthened by root-level assignments as well.
+    /// This is useful for problems where var elimination blows up the number of clauses.
+    ///
+    /// # Errors
+    ///
+    /// `SolverError::Inconsistent` if the formula is found unsatisfiable.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((
+    ///     Config::default(),
+    ///     vec![vec![1, 2], vec![1, 2, 3], vec![-1, 2, 3, 4], vec![2, 3, -4]].as_ref(),
+    /// ))
+    /// .expect("panic");
+    /// assert_eq!(s.subsume_only(), Ok(1));
+    /// assert_eq!(s.subsume_only(), Ok(0));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn subsume_only(&mut self) -> Result<usize, SolverError> {
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref mut state,
+        } = self;
+        debug_assert_eq!(asg.decision_level(), asg.root_level());
+        let mut elim = eliminator_on_demand(state);
+        match elim.subsume_all(asg, cdb) {
+            Ok(n) => {
+                state[Stat::SubsumedClause] += n;
+                Ok(n)
+            }
+            Err(SolverError::OutOfMemory) => Err(SolverError::OutOfMemory),
+            Err(_) => Err(SolverError::Inconsistent),
+        }
+    }
+    /// assert every pure literal, which occurs only positively or only negatively, at the
+    /// root level and remove the clauses satisfied by it, until no new pure literal
+    /// appears. It returns the number of asserted literals. Frozen vars are skipped.
+    /// Since these assertions are permanent, don't use it if you will add clauses
+    /// containing the complements later; freeze such vars beforehand.
+    ///
+    /// # Errors
+    ///
+    /// `SolverError::Inconsistent` if the formula is found unsatisfiable.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((
+    ///     Config::default(),
+    ///     vec![vec![1, 2, 3], vec![1, -2, -3], vec![-2, 3, 4], vec![2, -3, 4]].as_ref(),
+    /// ))
+    /// .expect("panic");
+    /// // 1 and 4 are pure; then 2 and 3 become free.
+    /// assert_eq!(s.eliminate_pure_literals(), Ok(2));
+    /// assert!(s.is_asserted(1) && s.is_asserted(4));
+    /// assert_eq!(s.eliminate_pure_literals(), Ok(0));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn eliminate_pure_literals(&mut self) -> Result<usize, SolverError> {
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref state,
+        } = self;
+        debug_assert_eq!(asg.decision_level(), asg.root_level());
+        let mut elim = eliminator_on_demand(state);
+        match elim.eliminate_pure_literals(asg, cdb) {
+            Ok(n) => Ok(n),
+            Err(SolverError::OutOfMemory) => Err(SolverError::OutOfMemory),
+            Err(_) => Err(SolverError::Inconsistent),
+        }
+    }
+    /// remove clauses satisfied at the root level and strip root-false literals from
+    /// the others, until no new unit clause appears. This is much cheaper than
+    /// [`preprocess`](`Solver::preprocess`). Root-level assignments are kept.
+    ///
+    /// # Errors
+    ///
+    /// `SolverError::Inconsistent` if the formula is found unsatisfiable.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((
+    ///     Config::default(),
+    ///     vec![vec![1, 2], vec![-1, 2, 3], vec![-2, 3, 4], vec![-3, -4, 5]].as_ref(),
+    /// ))
+    /// .expect("panic");
+    /// s.add_assignment(1).expect("panic");
+    /// s.add_assignment(-5).expect("panic");
+    /// assert!(s.remove_satisfied().is_ok());
+    /// let mut clauses = s.cdb.iter_active().map(|(_, c)| c.len()).collect::<Vec<_>>();
+    /// clauses.sort();
+    /// assert_eq!(clauses, vec![2, 2, 3]);
+    /// assert!(s.is_asserted(1));
+    ///
+    /// s.add_assignment(-2).expect("panic");
+    /// s.add_assignment(-3).expect("panic");
+    /// assert_eq!(s.remove_satisfied(), Err(SolverError::Inconsistent));
+    /// ```
+    pub fn remove_satisfied(&mut self) -> MaybeInconsistent {
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ..
+        } = self;
+        debug_assert_eq!(asg.decision_level(), asg.root_level());
+        loop {
+            asg.propagate_sandbox(cdb)
+                .map_err(|_| SolverError::Inconsistent)?;
+            let mut found_unit = false;
+            for ci in 1..cdb.len() {
+                let cid = ClauseId::from(ci);
+                if cdb[cid].is_dead() {
+                    continue;
+                }
+                match cdb.transform_by_simplification(asg, cid) {
+                    RefClause::EmptyClause => return Err(SolverError::Inconsistent),
+                    RefClause::UnitClause(lit) => {
+                        cdb.certificate_add_assertion(lit);
+                        asg.assign_at_root_level(lit)
+                            .map_err(|_| SolverError::Inconsistent)?;
+                        cdb.remove_clause(cid);
+                        found_unit = true;
+                    }
+                    _ => (),
+                }
+            }
+            if !found_unit {
+                return Ok(());
+            }
+        }
+    }
+    /// assume `l` tentatively, run unit propagation, and return the implied literals,
+    /// excluding `l` itself. The solver goes back to the root level before returning.
+    /// Un-propagated root-level assignments are propagated at the root level beforehand.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::Inconsistent` if `l` is a failed literal; so `!l` can be asserted.
+    ///   This includes the case that `l` is already falsified at the root level.
+    /// * `SolverError::InvalidLiteral` if the var of `l` is out of range or eliminated.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::{types::*, *};
+    ///
+    /// let mut s = Solver::try_from((
+    ///     Config::default(),
+    ///     vec![vec![-1, 2], vec![-2, 3], vec![-4, 5], vec![-4, -5]].as_ref(),
+    /// ))
+    /// .expect("panic");
+    /// let mut implied = s.probe(Lit::from(1)).expect("panic");
+    /// implied.sort();
+    /// assert_eq!(implied, vec![Lit::from(2), Lit::from(3)]);
+    /// assert_eq!(s.probe(Lit::from(4)), Err(SolverError::Inconsistent));
+    /// assert_eq!(s.probe(Lit::from(-4)), Ok(vec![]));
+    /// assert!(!s.is_asserted(4));
+    /// ```
+    pub fn probe(&mut self, l: Lit) -> Result<Vec<Lit>, SolverError> {
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ..
+        } = self;
+        debug_assert_eq!(asg.decision_level(), asg.root_level());
+        if l.vi() == 0 || asg.num_vars < l.vi() || asg.var(l.vi()).is(FlagVar::ELIMINATED) {
+            return Err(SolverError::InvalidLiteral);
+        }
+        if asg.remains() {
+            asg.propagate_sandbox(cdb)
+                .map_err(|_| SolverError::Inconsistent)?;
+        }
+        match asg.assigned(l) {
+            Some(true) => return Ok(Vec::new()),
+            Some(false) => return Err(SolverError::Inconsistent),
+            None => (),
+        }
+        let start = asg.stack_len();
+        asg.assign_by_decision(l);
+        let result = asg.propagate(cdb);
+        let implied = asg.stack_range(start + 1..asg.stack_len()).to_vec();
+        asg.cancel_until(asg.root_level());
+        result.map_or(Err(SolverError::Inconsistent), |_| Ok(implied))
+    }
+    /// probe each unassigned literal at the root level, and add a binary clause `(¬d ∨ m)`
+    /// as a learnt clause for each literal `m` implied by a clause with two or more
+    /// antecedents at the probing level, where `d` is their nearest dominator in the
+    /// implication tree. Failed literals found on the way are asserted negatively.
+    /// The work is bounded by `Config::hbr_prop_lim`. It returns the number of added clauses.
+    ///
+    /// # Errors
+    ///
+    /// `SolverError::Inconsistent` if the problem turns out to be unsatisfiable.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((
+    ///     Config::default(),
+    ///     vec![vec![-1, 2], vec![-1, 3], vec![-2, -3, 4], vec![-5, 6], vec![-5, -6]].as_ref(),
+    /// ))
+    /// .expect("panic");
+    /// assert_eq!(s.hyper_binary_resolution(), Ok(1));
+    /// assert!(s.is_asserted(5));
+    /// assert!(!s.is_asserted(4));
+    /// assert_eq!(s.hyper_binary_resolution(), Ok(0));
+    /// ```
+    pub fn hyper_binary_resolution(&mut self) -> Result<usize, SolverError> {
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref state,
+        } = self;
+        debug_assert_eq!(asg.decision_level(), asg.root_level());
+        if asg.remains() {
+            asg.propagate_sandbox(cdb)
+                .map_err(|_| SolverError::Inconsistent)?;
+        }
+        let limit =
+            asg.derefer(assign::property::Tusize::NumPropagation) + state.config.hbr_prop_lim;
+        let root = asg.root_level();
+        let mut parent: Vec<Lit> = vec![Lit::from(1i32); asg.num_vars + 1];
+        let mut depth: Vec<usize> = vec![0; asg.num_vars + 1];
+        let mut hyper_binaries: Vec<(Lit, Lit)> = Vec::new();
+        let mut num_added = 0;
+        'next_var: for vi in 1..=asg.num_vars {
+            for l in [Lit::new(vi, true), Lit::new(vi, false)] {
+                if limit <= asg.derefer(assign::property::Tusize::NumPropagation) {
+                    break 'next_var;
+                }
+                if asg.assign(vi).is_some() || asg.var(vi).is(FlagVar::ELIMINATED) {
+                    continue 'next_var;
+                }
+                let start = asg.stack_len();
+                asg.assign_by_decision(l);
+                if asg.propagate(cdb).is_err() {
+                    asg.cancel_until(root);
+                    cdb.certificate_add_assertion(!l);
+                    asg.assign_at_root_level(!l)
+                        .map_err(|_| SolverError::Inconsistent)?;
+                    asg.propagate_sandbox(cdb)
+                        .map_err(|_| SolverError::Inconsistent)?;
+                    continue 'next_var;
+                }
+                let lv = root + 1;
+                parent[vi] = l;
+                depth[vi] = 0;
+                for m in asg.stack_range(start + 1..asg.stack_len()).iter() {
+                    let dominator = match asg.reason(m.vi()) {
+                        AssignReason::BinaryLink(a) if asg.level(a.vi()) == lv => a,
+                        AssignReason::Implication(cid) => {
+                            let mut dominator: Option<Lit> = None;
+                            let mut num_antecedents = 0;
+                            for x in cdb[cid].iter() {
+                                if x.vi() == m.vi() || asg.level(x.vi()) != lv {
+                                    continue;
+                                }
+                                num_antecedents += 1;
+                                let mut a = !*x;
+                                let Some(mut b) = dominator else {
+                                    dominator = Some(a);
+                                    continue;
+                                };
+                                while a != b {
+                                    if depth[b.vi()] <= depth[a.vi()] {
+                                        a = parent[a.vi()];
+                                    } else {
+                                        b = parent[b.vi()];
+                                    }
+                                }
+                                dominator = Some(a);
+                            }
+                            let d = dominator.unwrap_or(l);
+                            if 1 < num_antecedents {
+                                hyper_binaries.push((!d, *m));
+                            }
+                            d
+                        }
+                        _ => l,
+                    };
+                    parent[m.vi()] = dominator;
+                    depth[m.vi()] = depth[dominator.vi()] + 1;
+                }
+                asg.cancel_until(root);
+                for (l0, l1) in hyper_binaries.drain(..) {
+                    if let RefClause::Clause(_) = cdb.new_clause(asg, &mut vec![l0, l1], true) {
+                        num_added += 1;
+                    }
+                }
+            }
+        }
+        Ok(num_added)
+    }
+    /// resolve pairs of ternary clauses, and add each resolvent which has three or fewer
+    /// literals and isn't subsumed by a clause of length three or less, as a learnt clause.
+    /// A binary resolvent is stored in the binary links like any binary clause.
+    /// Clauses which have an assigned literal at the root level are skipped, and so are
+    /// the resolvents added in this pass. The work is bounded by `Config::htr_res_lim`.
+    /// It returns the number of added clauses.
+    ///
+    /// # Errors
+    ///
+    /// `SolverError::Inconsistent` if the problem turns out to be unsatisfiable.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((
+    ///     Config::default(),
+    ///     vec![vec![1, 2, 3], vec![-1, 2, 3], vec![1, 4, 5], vec![-1, 4, 6]].as_ref(),
+    /// ))
+    /// .expect("panic");
+    /// assert_eq!(s.hyper_ternary_resolution(), Ok(2));
+    /// assert_eq!(s.hyper_ternary_resolution(), Ok(0));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn hyper_ternary_resolution(&mut self) -> Result<usize, SolverError> {
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref state,
+        } = self;
+        debug_assert_eq!(asg.decision_level(), asg.root_level());
+        if asg.remains() {
+            asg.propagate_sandbox(cdb)
+                .map_err(|_| SolverError::Inconsistent)?;
+        }
+        // the sorted clauses of length three or less, to check subsumption
+        let mut known: HashSet<Vec<Lit>> = HashSet::new();
+        let mut ternaries: Vec<[Lit; 3]> = Vec::new();
+        let mut occurs: Vec<Vec<usize>> = vec![Vec::new(); 2 * (asg.num_vars + 1)];
+        for (_, lits) in cdb.iter_active() {
+            if 3 < lits.len() || lits.iter().any(|l| asg.assigned(*l).is_some()) {
+                continue;
+            }
+            let mut key = lits.to_vec();
+            key.sort_unstable();
+            if let [a, b, c] = key[..] {
+                for l in [a, b, c] {
+                    occurs[usize::from(l)].push(ternaries.len());
+                }
+                ternaries.push([a, b, c]);
+            }
+            known.insert(key);
+        }
+        let is_subsumed = |known: &HashSet<Vec<Lit>>, r: &[Lit]| {
+            known.contains(r)
+                || (r.len() == 3
+                    && [[r[0], r[1]], [r[0], r[2]], [r[1], r[2]]]
+                        .iter()
+                        .any(|b| known.contains(&b[..])))
+        };
+        let mut budget = state.config.htr_res_lim;
+        let mut resolvents: Vec<Vec<Lit>> = Vec::new();
+        'next_var: for vi in 1..=asg.num_vars {
+            let pos = &occurs[usize::from(Lit::from((vi, true)))];
+            let neg = &occurs[usize::from(Lit::from((vi, false)))];
+            for i in pos.iter() {
+                for j in neg.iter() {
+                    if budget == 0 {
+                        break 'next_var;
+                    }
+                    budget -= 1;
+                    let mut r = ternaries[*i]
+                        .iter()
+                        .chain(ternaries[*j].iter())
+                        .filter(|l| l.vi() != vi)
+                        .copied()
+                        .collect::<Vec<Lit>>();
+                    r.sort_unstable();
+                    r.dedup();
+                    // complementary literals are adjacent after sorting.
+                    if 3 < r.len() || r.windows(2).any(|w| w[0] == !w[1]) || is_subsumed(&known, &r)
+                    {
+                        continue;
+                    }
+                    known.insert(r.clone());
+                    resolvents.push(r);
+                }
+            }
+        }
+        let mut num_added = 0;
+        for mut r in resolvents {
+            if let RefClause::Clause(_) = cdb.new_clause(asg, &mut r, true) {
+                num_added += 1;
+            }
+        }
+        Ok(num_added)
+    }
+    /// run a round of clause vivification on learnt clauses, and original ones as well
+    /// if `with_originals`, then return the number of removed literals.
+    /// Clauses with smaller LBDs are tried first until the number of assignments by
+    /// probing reaches `budget`. It returns at the root level.
+    ///
+    /// # Errors
+    ///
+    /// `SolverError::Inconsistent` if the formula is found unsatisfiable.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((
+    ///     Config::default(),
+    ///     vec![vec![-1, -4, 5], vec![-1, 2], vec![-2, 3], vec![-2, -3]].as_ref(),
+    /// ))
+    /// .expect("panic");
+    /// assert_eq!(s.vivify(1000, false), Ok(0));
+    /// assert!(0 < s.vivify(1000, true).expect("panic"));
+    /// assert!(s.is_asserted(1));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn vivify(&mut self, budget: usize, with_originals: bool) -> Result<usize, SolverError> {
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref mut state,
+        } = self;
+        debug_assert_eq!(asg.decision_level(), asg.root_level());
+        if asg.remains() {
+            asg.propagate_sandbox(cdb)
+                .map_err(|_| SolverError::Inconsistent)?;
+        }
+        let mut clauses = cdb
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, c)| !c.is_dead() && (with_originals || c.is(FlagClause::LEARNT)))
+            .map(|(i, c)| OrderedProxy::new_invert(ClauseId::from(i), c.rank as f64))
+            .collect::<Vec<_>>();
+        clauses.sort();
+        match cdb.vivify_clauses(asg, state, clauses, budget) {
+            Ok(n) => Ok(n),
+            Err(SolverError::OutOfMemory) => Err(SolverError::OutOfMemory),
+            Err(_) => Err(SolverError::Inconsistent),
+        }
+    }
+    /// return the 1-based indices of an approximate minimal unsatisfiable subset of the
+    /// given clauses. It returns `None` if `Config::self_check` is off, or `Some` of an
+    /// empty vector if the given clauses are satisfiable.
+    /// `Config::self_check` records the given clauses; the indices
+    /// count them in the order they were given, including units by `add_assignment`
+    /// and excluding duplicates dropped under `Config::dedup_clauses`.
+    /// It tries to remove each clause in turn: a clause is kept if the others become
+    /// satisfiable, or the trial stopped by a budget of conflicts. So the result is
+    /// unsatisfiable, but not always minimal, nor minimum.
+    /// The trials are done by [`solve_under_assumptions`](`Solver::solve_under_assumptions`)
+    /// on another solver in which each clause has a selector var. `self` is unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let config = Config {
+    ///     self_check: true,
+    ///     quiet_mode: true,
+    ///     ..Config::default()
+    /// };
+    /// let clauses = vec![vec![1, 2], vec![-1, 3], vec![-3], vec![-2, 4], vec![1, -4], vec![2, 5]];
+    /// let mut s = Solver::try_from((config, clauses.as_ref())).expect("panic");
+    /// assert_eq!(s.approximate_mus(), Some(vec![1, 2, 3, 4, 5]));
+    ///
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// assert_eq!(s.approximate_mus(), None);
+    /// ```
+    pub fn approximate_mus(&mut self) -> Option<Vec<usize>> {
+        /// the max number of conflicts in a trial
+        const BUDGET: usize = 10_000;
+        if !self.state.config.self_check {
+            return None;
+        }
+        let num_clauses = self.state.original_clauses.len();
+        let num_vars = self.asg.num_vars;
+        let config = Config {
+            max_conflicts: Some(BUDGET),
+            quiet_mode: true,
+            self_check: false,
+            use_certification: false,
+            ..self.state.config.clone()
+        };
+        let cnf = CNFDescription {
+            num_of_variables: num_vars + num_clauses,
+            ..CNFDescription::default()
+        };
+        let mut s = Solver::instantiate(&config, &cnf);
+        for (i, c) in self.state.original_clauses.iter().enumerate() {
+            let selector = Lit::from((num_vars + i + 1, true));
+            s.freeze(selector.vi());
+            let mut clause = c.iter().map(|l| i32::from(*l)).collect::<Vec<i32>>();
+            clause.push(-i32::from(selector));
+            // It can't be empty nor falsified as the selector is free.
+            let added = s.add_clause(clause);
+            debug_assert!(added.is_ok());
+        }
+        let mut core = vec![true; num_clauses];
+        let mut is_unsat = |core: &[bool]| -> bool {
+            for (i, _) in core.iter().enumerate().filter(|(_, b)| **b) {
+                s.push_assumption(Lit::from((num_vars + i + 1, true)));
+            }
+            let result = s.solve_under_assumptions();
+            while s.pop_assumption().is_some() {}
+            result == Ok(Certificate::UNSAT)
+        };
+        if num_clauses == 0 || !is_unsat(&core) {
+            return Some(Vec::new());
+        }
+        for i in 0..num_clauses {
+            core[i] = false;
+            if !is_unsat(&core) {
+                core[i] = true;
+            }
+        }
+        Some((1..=num_clauses).filter(|i| core[i - 1]).collect())
+    }
+}
+
+/// return an eliminator which works regardless of `Config::enable_eliminator`,
+/// for a pass called on demand.
+fn eliminator_on_demand(state: &State) -> Eliminator {
+    let config = Config {
+        enable_eliminator: true,
+        ..state.config.clone()
+    };
+    Eliminator::instantiate(&config, &state.cnf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "no_IO"))]
+    use crate::solver::SolveIF;
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_approximate_mus() {
+        let config = Config {
+            quiet_mode: true,
+            ..Config::from("cnfs/unsat.cnf")
+        };
+        let mut s = Solver::build(&config).expect("failed to load");
+        assert_eq!(s.approximate_mus(), None);
+        let config = Config {
+            self_check: true,
+            ..config
+        };
+        let mut s = Solver::build(&config).expect("failed to load");
+        let mus = s.approximate_mus().expect("self_check is on");
+        assert!(!mus.is_empty());
+        let clauses = mus
+            .iter()
+            .map(|i| {
+                s.state.original_clauses[i - 1]
+                    .iter()
+                    .map(|l| i32::from(*l))
+                    .collect::<Vec<i32>>()
+            })
+            .collect::<Vec<_>>();
+        let config = Config {
+            quiet_mode: true,
+            ..Config::default()
+        };
+        for i in 0..=clauses.len() {
+            let mut subset = clauses.clone();
+            if i < clauses.len() {
+                subset.remove(i);
+            }
+            let result = match Solver::try_from((config.clone(), subset.as_ref())) {
+                Ok(mut t) => t.solve(),
+                Err(_) => Ok(Certificate::UNSAT),
+            };
+            assert_eq!(result == Ok(Certificate::UNSAT), i == clauses.len());
+        }
+        let mut s = Solver::build(&Config {
+            self_check: true,
+            quiet_mode: true,
+            ..Config::from("cnfs/uf8.cnf")
+        })
+        .expect("failed to load");
+        assert_eq!(s.approximate_mus(), Some(Vec::new()));
+    }
+    #[test]
+    fn test_hyper_ternary_resolution() {
+        use crate::types::PropertyDereference;
+        let clauses = vec![vec![1, 2, 3], vec![-1, 2, 3], vec![2, -3, 4]];
+        let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+        // (2 3) on var 1, and (1 2 4) and (-1 2 4) on var 3
+        assert_eq!(s.hyper_ternary_resolution(), Ok(3));
+        // the binary resolvent (2 3) works as a binary link.
+        assert_eq!(s.cdb.derefer(crate::cdb::property::Tusize::NumBiClause), 1);
+        assert!(s.assert_and_propagate(&[-2]).is_ok());
+        assert!(s.is_asserted(3));
+        assert!(s.is_asserted(4));
+        #[cfg(not(feature = "no_IO"))]
+        {
+            use crate::solver::ValidateIF;
+            let path = std::path::Path::new("cnfs/uf100-010.cnf");
+            let config = Config {
+                quiet_mode: true,
+                ..Config::from(path)
+            };
+            let mut s = Solver::build(&config).expect("failed to load");
+            assert!(0 < s.hyper_ternary_resolution().expect("panic"));
+            assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+            assert_eq!(s.validate_against(path), Ok(()));
+        }
+    }
+}
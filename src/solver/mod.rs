@@ -3,6 +3,10 @@
 mod build;
 /// Module 'conflict' handles conflicts.
 mod conflict;
+/// Module `inprocess` provides simplification passes callable from outside
+mod inprocess;
+/// Module `profile` provides structural statistics of a problem.
+mod profile;
 /// Module `restart` provides restart heuristics.
 pub mod restart;
 /// CDCL search engine
@@ -14,27 +18,35 @@ mod validate;
 
 pub use self::{
     build::SatSolverIF,
-    restart::{RestartIF, RestartManager},
+    profile::ProblemProfile,
+    restart::{RestartCallback, RestartIF, RestartManager},
     search::SolveIF,
     stage::StageManager,
-    validate::ValidateIF,
+    validate::{ValidateIF, ValidationError},
 };
 
 use crate::{assign::AssignStack, cdb::ClauseDB, state::*, types::*};
 
 /// Normal results returned by Solver.
+///
+/// With feature `serde`, it is serialized in the externally tagged form of serde:
+/// `{"SAT":[1,-2,3]}`, `"UNSAT"` or `"UNKNOWN"` in JSON.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Certificate {
     /// It is satisfiable; `vec` is such an assignment sorted by var order.
     SAT(Vec<i32>),
     /// It is unsatisfiable.
     UNSAT,
+    /// It is undetermined due to timeout or interruption.
+    UNKNOWN,
 }
 
 /// The return type of `Solver::solve`.
-/// This captures the following three cases:
+/// This captures the following four cases:
 /// * `Certificate::SAT` -- solved with a satisfiable assignment set,
-/// * `Certificate::UNSAT` -- proved that it's an unsatisfiable problem, and
+/// * `Certificate::UNSAT` -- proved that it's an unsatisfiable problem,
+/// * `Certificate::UNKNOWN` -- stopped by timeout or interruption, and
 /// * `SolverError::*` -- caused by a bug
 pub type SolverResult = Result<Certificate, SolverError>;
 
@@ -63,6 +75,30 @@ pub enum SolverEvent {
     Vivify(bool),
 }
 
+/// An event recorded in [`Solver::trace`] if `Config::record_trace` is set.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TraceEvent {
+    /// assigning a literal by decision.
+    Decide(Lit),
+    /// a conflict found by unit propagation, with the falsified clause.
+    Conflict(ClauseId),
+    /// backjumping to a decision level after conflict analysis.
+    Backtrack(DecisionLevel),
+    /// restart
+    Restart,
+}
+
+/// The solver's estimate of the problem, returned by [`Solver::search_phase`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SearchPhase {
+    /// too few conflicts, or no clear trend.
+    Undetermined,
+    /// the trail is getting longer; the search is approaching a model.
+    SatLike,
+    /// the trail is getting shorter; the search is building a refutation.
+    UnsatLike,
+}
+
 /// The SAT solver object consisting of 6 sub modules.
 /// ```
 /// use crate::splr::*;
@@ -125,12 +161,729 @@ impl<V: AsRef<[i32]>> TryFrom<Vec<V>> for Certificate {
     }
 }
 
+impl Solver {
+    /// return `true` if the var is eliminated by the eliminator.
+    /// `solve` restores eliminated vars after finding a model.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let clauses = vec![vec![1, 3], vec![-3, 2], vec![2, 4], vec![-4, 1]];
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// s.freeze(4);
+    /// assert!(s.preprocess().is_ok());
+    /// assert!(s.is_eliminated(3));
+    /// assert!(!s.is_eliminated(4));
+    /// ```
+    pub fn is_eliminated(&self, vi: VarId) -> bool {
+        use crate::assign::VarManipulateIF;
+        self.asg.var(vi).is(FlagVar::ELIMINATED)
+    }
+    /// return the value of the var in the last model found by `solve`, including
+    /// the values of eliminated vars reconstructed by model extension.
+    /// It returns `None` before solving, after an unsatisfiable result, if `vi` is out of
+    /// range, or if the var is free: it occurs in no given clause nor given assignment.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((Config::default(), vec![vec![1, 2], vec![-1], vec![-2, 3]].as_ref()))
+    ///     .expect("panic");
+    /// assert_eq!(s.add_var(), 4);
+    /// assert_eq!(s.assign_value(2), None);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(v)) if v[..3] == [-1, 2, 3]));
+    /// assert_eq!(s.assign_value(1), Some(false));
+    /// assert_eq!(s.assign_value(3), Some(true));
+    /// // var 4 is free.
+    /// assert_eq!(s.assign_value(4), None);
+    /// assert_eq!(s.assign_value(5), None);
+    /// ```
+    pub fn assign_value(&self, vi: VarId) -> Option<bool> {
+        let i = vi.checked_sub(1)?;
+        let value = self.state.model.as_ref()?.get(i).map(|l| 0 < *l)?;
+        self.state.is_input_var(vi).then_some(value)
+    }
+    /// return `true` if the var is assigned at the root level.
+    pub fn is_asserted(&self, vi: VarId) -> bool {
+        use crate::assign::{AssignIF, VarManipulateIF};
+        self.asg.assign(vi).is_some() && self.asg.level(vi) <= self.asg.root_level()
+    }
+    /// return the literals assigned at the root level in var index order: the given
+    /// units and the ones derived by propagation, preprocessing and search.
+    /// They are collected from the assignments since the trail is wiped at the root level.
+    /// Note: pure literals asserted by preprocessing are included; they keep
+    /// satisfiability but aren't consequences of the formula.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use crate::splr::types::Lit;
+    ///
+    /// let clauses = vec![vec![-1, 2], vec![1], vec![-2, 3, 4], vec![-2, -3, 4], vec![-4, 5, 6]];
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// assert_eq!(s.root_assignments(), vec![Lit::from(1)]);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert!(s.root_assignments().contains(&Lit::from(2)));
+    /// ```
+    pub fn root_assignments(&self) -> Vec<Lit> {
+        use crate::assign::VarManipulateIF;
+        (1..=self.asg.num_vars)
+            .filter(|vi| self.is_asserted(*vi) && !self.is_eliminated(*vi))
+            .filter_map(|vi| self.asg.assign(vi).map(|b| Lit::from((vi, b))))
+            .collect()
+    }
+    /// return a partial model of the last model found by `solve`, which drops the
+    /// vars whose values don't matter: a var becomes a don't-care if every clause
+    /// satisfied by it is also satisfied by another literal in the partial model.
+    /// The vars are tried greedily in var index order, so the result is minimal
+    /// (no more var can be dropped) but not minimum.
+    /// The scan is over the irredundant clauses in the clause DB. Vars asserted at
+    /// the root level and vars occurring in the clauses removed by elimination are
+    /// kept, since the clauses removed by them aren't in the clause DB.
+    /// It returns an empty vector if there's no model.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let clauses = vec![vec![1, 2], vec![-1, 3], vec![2, 3]];
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// assert!(s.minimal_model().is_empty());
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// let model = s.minimal_model();
+    /// assert!(clauses.iter().all(|c| c.iter().any(|l| model.contains(l))));
+    /// ```
+    pub fn minimal_model(&self) -> Vec<i32> {
+        let Some(ref model) = self.state.model else {
+            return Vec::new();
+        };
+        let satisfies = |l: &Lit| model.get(l.vi() - 1) == Some(&i32::from(*l));
+        let mut keep = (0..=model.len())
+            .map(|vi| 0 < vi && self.is_asserted(vi))
+            .collect::<Vec<bool>>();
+        for l in self.asg.eliminated.iter() {
+            if let Some(k) = keep.get_mut(l.vi()) {
+                *k = true;
+            }
+        }
+        // the number of satisfied literals of each clause, and
+        // the clauses each var satisfies
+        let mut num_sat: Vec<usize> = Vec::new();
+        let mut occurs: Vec<Vec<usize>> = vec![Vec::new(); model.len() + 1];
+        for (cid, lits) in self.cdb.iter_active() {
+            if self.cdb[cid].is(FlagClause::LEARNT) {
+                continue;
+            }
+            let i = num_sat.len();
+            num_sat.push(0);
+            for l in lits.iter().filter(|l| satisfies(l)) {
+                num_sat[i] += 1;
+                occurs[l.vi()].push(i);
+            }
+        }
+        for vi in 1..=model.len() {
+            if !keep[vi] && occurs[vi].iter().all(|i| 1 < num_sat[*i]) {
+                for i in occurs[vi].iter() {
+                    num_sat[*i] -= 1;
+                }
+            } else {
+                keep[vi] = true;
+            }
+        }
+        model
+            .iter()
+            .filter(|l| keep[l.unsigned_abs() as usize])
+            .copied()
+            .collect()
+    }
+    /// return the number of conflicts since the last restart. O(1).
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use std::path::Path;
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert_eq!(s.conflicts_since_last_restart(), 0);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert!(s.conflicts_since_last_restart() <= s.asg.num_conflict);
+    /// ```
+    pub fn conflicts_since_last_restart(&self) -> usize {
+        self.state.restart.conflicts_since_restart()
+    }
+    /// return the current decision level. It is the root level outside of `solve`. O(1).
+    pub fn decision_level(&self) -> DecisionLevel {
+        use crate::assign::AssignIF;
+        self.asg.decision_level()
+    }
+    /// return the estimate of the search phase from the short and long term EMAs of
+    /// the trail sizes at conflicts. It is `Undetermined` for the first conflicts,
+    /// as many as the long window. O(1).
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use crate::splr::solver::SearchPhase;
+    /// use std::path::Path;
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert_eq!(s.search_phase(), SearchPhase::Undetermined);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn search_phase(&self) -> SearchPhase {
+        const WARMUP: usize = 5_000;
+        const MARGIN: f64 = 0.05;
+        if self.asg.num_conflict < WARMUP {
+            return SearchPhase::Undetermined;
+        }
+        match self.state.trail_ema.trend() {
+            t if 1.0 + MARGIN < t => SearchPhase::SatLike,
+            t if t < 1.0 - MARGIN => SearchPhase::UnsatLike,
+            _ => SearchPhase::Undetermined,
+        }
+    }
+    /// return the number of assigned literals on the trail. O(1).
+    /// Note: root-level assignments may have been cleared from the trail;
+    /// use [`root_assignments`](`Solver::root_assignments`) for them.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use crate::splr::assign::PropagateIF;
+    /// use crate::splr::types::Lit;
+    ///
+    /// let clauses = vec![vec![-1, 2], vec![-2, 3], vec![3, 4]];
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// assert_eq!((s.decision_level(), s.trail_len()), (0, 0));
+    /// s.asg.assign_by_decision(Lit::from(1));
+    /// assert!(s.asg.propagate(&mut s.cdb).is_ok());
+    /// assert_eq!((s.decision_level(), s.trail_len()), (1, 3));
+    /// ```
+    pub fn trail_len(&self) -> usize {
+        use crate::assign::AssignIF;
+        self.asg.stack_len()
+    }
+    /// return the current restart interval. As restarts are driven by LBD and entropy
+    /// trends rather than by a fixed schedule, this is the moving average of the number
+    /// of conflicts between restarts. O(1).
+    pub fn restart_interval(&self) -> f64 {
+        use crate::types::PropertyReference;
+        self.asg
+            .refer(crate::assign::property::TEma::ConflictPerRestart)
+            .get()
+    }
+    /// return the number of learnt clauses in the clause database. O(1).
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use std::path::Path;
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert_eq!(s.num_learnt(), 0);
+    /// assert_eq!(s.decision_level(), 0);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert_eq!(s.decision_level(), 0);
+    /// assert!(0.0 <= s.restart_interval());
+    /// ```
+    pub fn num_learnt(&self) -> usize {
+        use crate::types::PropertyDereference;
+        self.cdb.derefer(crate::cdb::property::Tusize::NumLearnt)
+    }
+    /// return a snapshot of the current trail: each assigned literal with its reason and
+    /// decision level, in assignment order. Note: `solve` leaves the solver at the root level.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use crate::splr::assign::{AssignReason, PropagateIF};
+    /// use crate::splr::types::Lit;
+    ///
+    /// let clauses = vec![vec![-1, 2], vec![-1, -2, 3], vec![4]];
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// s.asg.assign_by_decision(Lit::from(1));
+    /// assert!(s.asg.propagate(&mut s.cdb).is_ok());
+    /// let graph = s.implication_graph();
+    /// assert_eq!(graph.len(), 4);
+    /// assert_eq!(graph[0], (Lit::from(4), AssignReason::Decision(0), 0));
+    /// assert_eq!(graph[1], (Lit::from(1), AssignReason::Decision(1), 1));
+    /// assert!(matches!(graph[2], (l, AssignReason::BinaryLink(_), 1) if l == Lit::from(2)));
+    /// assert!(matches!(graph[3], (l, AssignReason::Implication(_), 1) if l == Lit::from(3)));
+    /// ```
+    pub fn implication_graph(&self) -> Vec<(Lit, AssignReason, DecisionLevel)> {
+        use crate::assign::{AssignIF, VarManipulateIF};
+        self.asg
+            .stack_iter()
+            .map(|l| (*l, self.asg.reason(l.vi()), self.asg.level(l.vi())))
+            .collect()
+    }
+    /// return the current implication graph in GraphViz DOT format.
+    /// Each node is an assigned literal labeled with its decision level; decisions are
+    /// drawn as boxes. Edges go from the antecedents to the implied literal.
+    /// If a clause is falsified, it's drawn as a red node with edges from its literals.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use crate::splr::assign::PropagateIF;
+    /// use crate::splr::types::Lit;
+    ///
+    /// let clauses = vec![vec![-1, 2], vec![-1, -2, 3], vec![-3, -2]];
+    /// let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+    /// s.asg.assign_by_decision(Lit::from(1));
+    /// assert!(s.asg.propagate(&mut s.cdb).is_err());
+    /// let dot = s.implication_graph_dot();
+    /// assert!(dot.starts_with("digraph implication_graph {"));
+    /// assert!(dot.contains("v1 [label=\"1@1\", shape=box];"));
+    /// assert!(dot.contains("v1 -> v2;"));
+    /// assert!(dot.contains("conflict [label=\"conflict\", color=red"));
+    /// ```
+    pub fn implication_graph_dot(&self) -> String {
+        use crate::assign::{AssignIF, VarManipulateIF};
+        use std::fmt::Write;
+        let asg = &self.asg;
+        let mut dot = String::from("digraph implication_graph {\n");
+        for l in asg.stack_iter() {
+            let vi = l.vi();
+            let reason = asg.reason(vi);
+            let shape = if matches!(reason, AssignReason::Decision(_)) {
+                ", shape=box"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                dot,
+                "  v{vi} [label=\"{}@{}\"{shape}];",
+                i32::from(*l),
+                asg.level(vi),
+            );
+            match reason {
+                AssignReason::BinaryLink(from) => {
+                    let _ = writeln!(dot, "  v{} -> v{vi};", from.vi());
+                }
+                AssignReason::Implication(cid) => {
+                    for x in self.cdb[cid].iter().filter(|x| x.vi() != vi) {
+                        let _ = writeln!(dot, "  v{} -> v{vi};", x.vi());
+                    }
+                }
+                AssignReason::Decision(_) | AssignReason::None => (),
+            }
+        }
+        if let Some((_, lits)) = self
+            .cdb
+            .iter_active()
+            .find(|(_, lits)| lits.iter().all(|l| asg.assigned(*l) == Some(false)))
+        {
+            dot.push_str("  conflict [label=\"conflict\", color=red, fontcolor=red];\n");
+            for l in lits.iter() {
+                let _ = writeln!(dot, "  v{} -> conflict [color=red];", l.vi());
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+    /// return the decisions, conflicts, backtracks and restarts in the last `solve` call.
+    /// It's empty unless `Config::record_trace` is set.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use crate::splr::{cdb::ClauseId, solver::TraceEvent};
+    /// use std::path::Path;
+    ///
+    /// let config = Config {
+    ///     record_trace: true,
+    ///     ..Config::from(Path::new("cnfs/uf100-010.cnf"))
+    /// };
+    /// let mut s = Solver::build(&config).expect("panic");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert!(matches!(s.trace().first(), Some(TraceEvent::Decide(_))));
+    /// assert!(s.trace().iter().any(|e| matches!(e, TraceEvent::Conflict(_))));
+    /// // every conflict has the falsified clause.
+    /// assert!(!s
+    ///     .trace()
+    ///     .iter()
+    ///     .any(|e| matches!(e, TraceEvent::Conflict(cid) if *cid == ClauseId::default())));
+    /// ```
+    pub fn trace(&self) -> &[TraceEvent] {
+        &self.state.trace
+    }
+    /// solve the problem and return the DRAT certification along with the result.
+    /// The certification is returned only for `UNSAT` under `Config::use_certification`
+    /// and `Config::proof_in_memory`; it is `None` otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let config = Config {
+    ///     use_certification: true,
+    ///     proof_in_memory: true,
+    ///     ..Config::default()
+    /// };
+    /// let clauses = vec![vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]];
+    /// let mut s = Solver::try_from((config, clauses.as_ref())).expect("panic");
+    /// let (result, proof) = s.solve_with_proof();
+    /// assert_eq!(result, Ok(Certificate::UNSAT));
+    /// # #[cfg(not(feature = "no_IO"))]
+    /// assert!(String::from_utf8(proof.expect("no proof")).unwrap().ends_with("0\n"));
+    ///
+    /// let mut s = Solver::try_from((Config::default(), vec![vec![1, 2]].as_ref())).expect("panic");
+    /// assert_eq!(s.solve_with_proof().1, None);
+    /// ```
+    pub fn solve_with_proof(&mut self) -> (SolverResult, Option<Vec<u8>>) {
+        use crate::cdb::ClauseDBIF;
+        let result = self.solve();
+        let proof = match result {
+            Ok(Certificate::UNSAT) => self.cdb.certificate_take(),
+            _ => None,
+        };
+        (result, proof)
+    }
+    /// add a clause, then propagate its consequences at the root level at once.
+    /// A clause that is unit, or becomes unit by the propagation of other root-level
+    /// assignments, is asserted. After a root-level conflict, `solve` returns `UNSAT`.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::Inconsistent` if the clause is falsified at the root level or the
+    ///   propagation derives a conflict.
+    /// * `SolverError::InvalidLiteral` if a literal in it is out of range for var index.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((
+    ///     Config::default(),
+    ///     vec![vec![-1, 2], vec![-2, 3, 4], vec![-3, 5]].as_ref(),
+    /// ))
+    /// .expect("panic");
+    /// assert!(s.assert_and_propagate(&[1]).is_ok());
+    /// assert!(s.is_asserted(2));
+    /// // [-2, -4] becomes unit after 2 is asserted, then 3 and 5 follow.
+    /// assert!(s.assert_and_propagate(&[-2, -4]).is_ok());
+    /// assert!(s.is_asserted(4) && s.is_asserted(3) && s.is_asserted(5));
+    /// assert_eq!(s.assert_and_propagate(&[-5, -1]), Err(SolverError::Inconsistent));
+    /// assert_eq!(s.assert_and_propagate(&[7]), Err(SolverError::InvalidLiteral));
+    /// ```
+    pub fn assert_and_propagate(&mut self, lits: &[i32]) -> MaybeInconsistent {
+        use crate::assign::PropagateIF;
+        match self.add_clause(lits) {
+            Ok(_) => (),
+            Err(SolverError::InvalidLiteral) => return Err(SolverError::InvalidLiteral),
+            Err(_) => return Err(SolverError::Inconsistent),
+        }
+        let Solver {
+            ref mut asg,
+            ref mut cdb,
+            ref mut state,
+        } = self;
+        if asg.propagate_sandbox(cdb).is_err() {
+            state.inconsistent = true;
+            return Err(SolverError::Inconsistent);
+        }
+        Ok(())
+    }
+    /// replace the wall clock used for timeout handling with `clock`, which returns
+    /// elapsed time in seconds. Timeout happens when it exceeds `Config::c_timeout`.
+    /// So a virtual clock, e.g. based on a counter, makes timeout deterministic.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use std::path::Path;
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/sample.cnf")).expect("panic");
+    /// s.set_clock(Box::new(|| f64::MAX));
+    /// assert_eq!(s.solve(), Ok(Certificate::UNKNOWN));
+    /// ```
+    pub fn set_clock(&mut self, clock: Box<dyn Fn() -> f64 + Send + Sync>) {
+        self.state.clock = Some(Clock(clock.into()));
+    }
+    /// call `f` on every restart with the number of restarts and the number of conflicts
+    /// since the previous restart. Restarts at stage boundaries and the final backtrack
+    /// to the root level at the end of `solve` are included.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use std::{path::Path, sync::{Arc, Mutex}};
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/sample.cnf")).expect("panic");
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let log_ = log.clone();
+    /// s.on_restart(Box::new(move |n, interval| log_.lock().unwrap().push((n, interval))));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// let log = log.lock().unwrap();
+    /// assert!(!log.is_empty());
+    /// assert!(log.iter().enumerate().all(|(i, (n, _))| i + 1 == *n));
+    /// ```
+    pub fn on_restart(&mut self, f: Box<dyn FnMut(usize, u32) + Send>) {
+        use std::sync::{Arc, Mutex};
+        self.state.restart.callback = Some(RestartCallback(Arc::new(Mutex::new(f))));
+    }
+    /// return a human-readable report on the current solver state: the target, the trail
+    /// grouped by decision levels, clause counters and the restart stage.
+    /// This is a diagnostic API; the solver never calls it internally.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((Config::default(), vec![vec![1, 2], vec![-1, 3, 4]].as_ref()))
+    ///     .expect("panic");
+    /// s.add_assignment(1).expect("panic");
+    /// let report = s.dump_state();
+    /// assert!(report.contains("ASG:: trail(1):[(0, [1])]"));
+    /// assert!(report.contains("CDB:: clauses: 2, learnts: 0"));
+    /// ```
+    pub fn dump_state(&self) -> String {
+        use crate::cdb;
+        let Solver { asg, cdb, state } = self;
+        format!(
+            "{}\n{}\nCDB:: clauses: {}, learnts: {}, binary clauses: {}, binary learnts: {}\n\
+             STM:: stage: {}, cycle: {}, segment: {}, span: {}, restarts: {}, energy: {:.4}",
+            state,
+            asg,
+            cdb.derefer(cdb::property::Tusize::NumClause),
+            cdb.derefer(cdb::property::Tusize::NumLearnt),
+            cdb.derefer(cdb::property::Tusize::NumBiClause),
+            cdb.derefer(cdb::property::Tusize::NumBiLearnt),
+            state.stm.current_stage(),
+            state.stm.current_cycle(),
+            state.stm.current_segment(),
+            state.stm.current_span(),
+            state[Stat::Restart],
+            state.restart.penetration_energy_charged,
+        )
+    }
+    /// return the header line of [`statistics_csv_row`](`Solver::statistics_csv_row`),
+    /// without a newline. The first four columns follow the benchmark tables in the
+    /// release notes: `solver, num, target, time`.
+    pub fn statistics_csv_header() -> &'static str {
+        "solver,num,target,time,conflicts,propagations,restarts"
+    }
+    /// return the stats of the current run as a CSV row without a newline: the solver
+    /// version, the instance number `num` given by the caller, the CNF file name (empty
+    /// if not loaded from a file), the elapsed time in seconds, and the numbers of
+    /// conflicts, propagations and restarts.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use std::path::Path;
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/uf8.cnf")).expect("can't load");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert_eq!(
+    ///     Solver::statistics_csv_header().split(',').take(4).collect::<Vec<_>>(),
+    ///     ["solver", "num", "target", "time"]
+    /// );
+    /// let row = s.statistics_csv_row(3);
+    /// assert!(row.starts_with(&format!("splr-{VERSION},3,uf8.cnf,")));
+    /// assert_eq!(row.split(',').count(), Solver::statistics_csv_header().split(',').count());
+    /// ```
+    pub fn statistics_csv_row(&self, num: usize) -> String {
+        use crate::assign;
+        let target = match &self.state.target.pathname {
+            CNFIndicator::File(f) if f.contains([',', '"']) => {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            }
+            CNFIndicator::File(f) => f.to_string(),
+            _ => String::new(),
+        };
+        format!(
+            "splr-{},{},{},{:.3},{},{},{}",
+            crate::VERSION,
+            num,
+            target,
+            self.state.elapsed_secs(),
+            self.asg.derefer(assign::property::Tusize::NumConflict),
+            self.asg.derefer(assign::property::Tusize::NumPropagation),
+            self.state[Stat::Restart],
+        )
+    }
+    /// return the activities of vars used in decision var selection.
+    /// It is indexed by `VarId`; so the 0th element is a dummy.
+    pub fn export_activities(&self) -> Vec<f64> {
+        (0..=self.asg.num_vars)
+            .map(|vi| self.asg.activity(vi))
+            .collect::<Vec<f64>>()
+    }
+    /// set the activities of vars to warm-start from [`export_activities`](`Solver::export_activities`)
+    /// on a related problem. The values are scaled down to fit in \[0, 1\] if some exceeds 1.
+    /// Negative, NaN or infinite values are regarded as 0. Eliminated vars and vars out of
+    /// range are ignored. The initial uniform activities set by `solve` are not applied
+    /// if some var has a non-zero activity.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use std::path::Path;
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/uf100-010.cnf")).expect("panic");
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// let act = s.export_activities();
+    /// assert_eq!(act.len(), 101);
+    ///
+    /// let mut t = Solver::try_from(Path::new("cnfs/uf100-010.cnf")).expect("panic");
+    /// t.import_activities(&[0.0, 4.0, f64::NAN, -1.0, 2.0]);
+    /// assert_eq!(&t.export_activities()[..6], &[0.0, 1.0, 0.0, 0.0, 0.5, 0.0]);
+    /// t.import_activities(&act);
+    /// assert!(matches!(t.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn import_activities(&mut self, activities: &[f64]) {
+        use crate::assign::{VarManipulateIF, VarSelectIF};
+        let scale = activities
+            .iter()
+            .skip(1)
+            .filter(|a| a.is_finite())
+            .fold(1.0_f64, |m, a| m.max(*a));
+        for (vi, a) in activities
+            .iter()
+            .enumerate()
+            .take(self.asg.num_vars + 1)
+            .skip(1)
+        {
+            if self.asg.var(vi).is(FlagVar::ELIMINATED) {
+                continue;
+            }
+            let val = if a.is_finite() && 0.0 < *a {
+                *a / scale
+            } else {
+                0.0
+            };
+            self.asg.set_activity(vi, val);
+        }
+        self.asg.rebuild_order();
+    }
+    /// give an initial static var order for the first decisions. Vars in `order` get
+    /// decreasing activities, which are higher than the default one used by `solve`.
+    /// Unlisted vars keep the default activity. Eliminated vars, vars out of range and
+    /// the second and later occurrences of a var are ignored. Call this before `solve`.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    /// use std::path::Path;
+    ///
+    /// let mut s = Solver::try_from(Path::new("cnfs/uf100-010.cnf")).expect("panic");
+    /// s.set_initial_order(&[30, 20, 10]);
+    /// let act = s.export_activities();
+    /// assert!(act[30] > act[20] && act[20] > act[10] && act[10] > act[1]);
+    /// assert_eq!(act[1], act[100]);
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn set_initial_order(&mut self, order: &[VarId]) {
+        use crate::assign::{VarManipulateIF, VarSelectIF};
+        let asg = &mut self.asg;
+        // the same value as the initial activity set by `solve`
+        let default = 1.0 / (asg.num_vars as f64).powf(0.25);
+        for vi in 1..=asg.num_vars {
+            if !asg.var(vi).is(FlagVar::ELIMINATED) && asg.activity(vi) == 0.0 {
+                asg.set_activity(vi, default);
+            }
+        }
+        let step = (1.0 - default) / order.len().max(1) as f64;
+        let mut listed = vec![false; asg.num_vars + 1];
+        for (i, vi) in order.iter().enumerate() {
+            if *vi == 0 || asg.num_vars < *vi || listed[*vi] || asg.var(*vi).is(FlagVar::ELIMINATED)
+            {
+                continue;
+            }
+            listed[*vi] = true;
+            asg.set_activity(*vi, 1.0 - step * i as f64);
+        }
+        asg.rebuild_order();
+    }
+    /// return an independent copy of the solver, which keeps the clauses, learnt ones
+    /// included, and the results of preprocessing. It can be driven on another thread.
+    /// `Clone` deep-copies everything but the handles shared by `Arc`; `fork` detaches
+    /// them: the interrupt flag and the restart callback. A fork doesn't write any
+    /// DRAT certification. As with `solve` itself, solving a fork of a solver which
+    /// has already solved requires feature `incremental_solver`.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let mut s = Solver::try_from((Config::default(), vec![vec![1, 2], vec![-1, 3]].as_ref()))
+    ///     .expect("panic");
+    /// let mut t = s.fork();
+    /// assert!(t.add_assignment(-3).is_ok());
+    /// let handle = std::thread::spawn(move || t.solve());
+    /// assert_eq!(s.add_assignment(-2).and_then(|s| s.solve()), Ok(Certificate::SAT(vec![1, -2, 3])));
+    /// assert_eq!(handle.join().expect("panic"), Ok(Certificate::SAT(vec![-1, 2, -3])));
+    /// ```
+    pub fn fork(&self) -> Solver {
+        let mut s = self.clone();
+        s.state.interrupt = None;
+        s.state.restart.callback = None;
+        s
+    }
+    /// push `l` onto the assumption stack used by
+    /// [`solve_under_assumptions`](`Solver::solve_under_assumptions`).
+    pub fn push_assumption(&mut self, l: Lit) {
+        self.state.assumptions.push(l);
+    }
+    /// remove the most recent assumption and return it.
+    pub fn pop_assumption(&mut self) -> Option<Lit> {
+        self.state.assumptions.pop()
+    }
+    /// solve the problem under all assumptions in the current stack.
+    /// The assumptions are decided before any other var; so the clauses learnt in this
+    /// search are valid without them and kept, as well as phases and var activities.
+    /// The solver backtracks to the root level after it.
+    /// Var elimination is off in this call, since it would remove clauses needed by
+    /// later calls. For the same reason, this should not follow a `solve` which
+    /// eliminated vars unless feature 'incremental_solver' is on.
+    /// No certificate is written even if the result is UNSAT.
+    /// After an UNSAT result, `state.conflicts` holds the assumptions used to refute them,
+    /// or nothing if the formula itself is unsatisfiable.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::InvalidLiteral` if an assumption is out of range or on an eliminated var.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::{types::*, *};
+    ///
+    /// let mut s = Solver::try_from((Config::default(), vec![vec![1, 2], vec![-1, 3]].as_ref()))
+    ///     .expect("panic");
+    /// s.push_assumption(Lit::from(-2));
+    /// assert!(matches!(s.solve_under_assumptions(), Ok(Certificate::SAT(v)) if v[0] == 1));
+    /// s.push_assumption(Lit::from(-3));
+    /// assert_eq!(s.solve_under_assumptions(), Ok(Certificate::UNSAT));
+    /// assert!(s.state.conflicts.contains(&Lit::from(-3)));
+    /// assert_eq!(s.pop_assumption(), Some(Lit::from(-3)));
+    /// assert!(matches!(s.solve_under_assumptions(), Ok(Certificate::SAT(_))));
+    /// ```
+    pub fn solve_under_assumptions(&mut self) -> SolverResult {
+        use crate::assign::VarManipulateIF;
+        let assumptions = self.state.assumptions.clone();
+        for l in assumptions.iter() {
+            let vi = l.vi();
+            if vi == 0 || self.asg.num_vars < vi || self.asg.var(vi).is(FlagVar::ELIMINATED) {
+                return Err(SolverError::InvalidLiteral);
+            }
+        }
+        let enable_eliminator = self.state.config.enable_eliminator;
+        self.state.config.enable_eliminator = false;
+        let result = self.solve_formula(&assumptions);
+        self.state.config.enable_eliminator = enable_eliminator;
+        result
+    }
+}
+
 /// Iterator for Solver
 /// * takes `&mut Solver`
 /// * returns `Option<Vec<i32>>`
 ///    * `Some(Vec<i32>)` -- satisfiable assignment
-///    * `None` -- unsatisfiable anymore
-/// * Some internal error causes panic.
+///    * `None` -- unsatisfiable anymore, timed out, or failed by an internal error
 #[cfg(feature = "incremental_solver")]
 pub struct SolverIter<'a> {
     solver: &'a mut Solver,
@@ -148,12 +901,115 @@ impl Solver {
     ///    println!(" - answer: {:?}", v);
     ///}
     ///```
-    pub fn iter(&mut self) -> SolverIter {
+    pub fn iter(&mut self) -> SolverIter<'_> {
         SolverIter {
             solver: self,
             refute: None,
         }
     }
+    /// return up to `max` distinct models in discovery order. **Requires 'incremental_solver' feature**
+    /// Each model is blocked by a clause over non-eliminated vars before the next search,
+    /// so that the extended models stay valid. It stops at timeout as well.
+    ///
+    /// # Errors
+    ///
+    /// * any error from [`solve`](`SolveIF::solve`) or from adding a blocking clause.
+    ///   The models found so far are dropped.
+    ///
+    ///```ignore
+    ///use splr::*;
+    ///
+    ///let mut s = Solver::try_from((Config::default(), vec![vec![1, 2]].as_ref())).expect("panic");
+    ///assert_eq!(s.solve_all(2).map(|v| v.len()), Ok(2));
+    ///assert_eq!(s.solve_all(10).map(|v| v.len()), Ok(1));
+    ///```
+    pub fn solve_all(&mut self, max: usize) -> Result<Vec<Vec<i32>>, SolverError> {
+        use crate::assign::VarManipulateIF;
+        let mut models = Vec::new();
+        while models.len() < max {
+            match self.solve() {
+                Ok(Certificate::SAT(ans)) => {
+                    let refute: Vec<i32> = ans
+                        .iter()
+                        .filter(|i| {
+                            !self
+                                .asg
+                                .var(i.unsigned_abs() as usize)
+                                .is(FlagVar::ELIMINATED)
+                        })
+                        .map(|i| -i)
+                        .collect::<Vec<i32>>();
+                    models.push(ans);
+                    if refute.is_empty() {
+                        break;
+                    }
+                    match self.add_clause(&refute) {
+                        Err(SolverError::Inconsistent)
+                        | Err(SolverError::EmptyClause)
+                        | Err(SolverError::RootLevelConflict(_)) => break,
+                        Err(e) => return Err(e),
+                        Ok(_) => self.reset(),
+                    }
+                }
+                Ok(Certificate::UNSAT | Certificate::UNKNOWN) => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(models)
+    }
+    /// return up to `max` models which are distinct over the projection vars `proj`.
+    /// **Requires 'incremental_solver' feature**
+    /// Each model is a vector of literals on `proj`. Since each model is blocked by a clause
+    /// over `proj`, the vars in it are frozen, or protected from the eliminator.
+    /// It stops at timeout as well.
+    ///
+    /// # Errors
+    ///
+    /// * `SolverError::InvalidLiteral` if a var in `proj` is out of range or already eliminated.
+    /// * any error from [`solve`](`SolveIF::solve`) or from adding a blocking clause.
+    ///
+    ///```ignore
+    ///use splr::*;
+    ///
+    ///let mut s = Solver::try_from((Config::default(), vec![vec![1, 2, 3]].as_ref())).expect("panic");
+    ///assert_eq!(s.solve_all_projected(&[1], 10), Ok(vec![vec![1], vec![-1]]));
+    ///```
+    pub fn solve_all_projected(
+        &mut self,
+        proj: &[VarId],
+        max: usize,
+    ) -> Result<Vec<Vec<i32>>, SolverError> {
+        use crate::assign::VarManipulateIF;
+        for vi in proj.iter() {
+            if *vi == 0 || self.asg.num_vars < *vi || self.asg.var(*vi).is(FlagVar::ELIMINATED) {
+                return Err(SolverError::InvalidLiteral);
+            }
+            self.freeze(*vi);
+        }
+        let mut models = Vec::new();
+        while models.len() < max {
+            match self.solve() {
+                Ok(Certificate::SAT(ans)) => {
+                    let model: Vec<i32> = proj.iter().map(|vi| ans[*vi - 1]).collect::<Vec<i32>>();
+                    let refute: Vec<i32> = model.iter().map(|i| -i).collect::<Vec<i32>>();
+                    models.push(model);
+                    if refute.is_empty() {
+                        break;
+                    }
+                    match self.add_clause(&refute) {
+                        Err(SolverError::Inconsistent)
+                        | Err(SolverError::EmptyClause)
+                        | Err(SolverError::RootLevelConflict(_)) => break,
+                        Err(e) => return Err(e),
+                        Ok(_) => self.reset(),
+                    }
+                }
+                Ok(Certificate::UNSAT | Certificate::UNKNOWN) => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(models)
+    }
 }
 
 #[cfg(feature = "incremental_solver")]
@@ -163,9 +1019,7 @@ impl<'a> Iterator for SolverIter<'a> {
         if let Some(ref v) = self.refute {
             debug_assert!(1 < v.len());
             match self.solver.add_clause(v) {
-                Err(SolverError::Inconsistent) => return None,
-                Err(SolverError::EmptyClause) => return None,
-                Err(e) => panic!("s UNKNOWN: {:?} by adding {:?}", e, v),
+                Err(_) => return None,
                 Ok(_) => self.solver.reset(),
             }
             self.refute = None;
@@ -176,8 +1030,7 @@ impl<'a> Iterator for SolverIter<'a> {
                 self.refute = Some(rft);
                 Some(ans)
             }
-            Ok(Certificate::UNSAT) => None,
-            e => panic!("s UNKNOWN: {:?}", e),
+            Ok(Certificate::UNSAT | Certificate::UNKNOWN) | Err(_) => None,
         }
     }
 }
@@ -185,9 +1038,11 @@ impl<'a> Iterator for SolverIter<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assign;
+    #[cfg(not(feature = "no_IO"))]
+    use crate::{assign, cdb};
 
-    #[cfg_attr(not(feature = "no_IO"), test)]
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
     fn test_solver() {
         let config = Config::from("cnfs/sample.cnf");
         if let Ok(s) = Solver::build(&config) {
@@ -201,6 +1056,172 @@ mod tests {
         }
     }
 
+    #[test]
+    // Solvers have no global state. So we can run them on multiple threads.
+    fn test_independent_solvers() {
+        let v1: Vec<Vec<i32>> = vec![vec![1, 2], vec![-1, 2], vec![1, -2]];
+        let v2: Vec<Vec<i32>> = vec![vec![-1, 3], vec![-2, -3], vec![2, 4], vec![-4, 1]];
+        let h1 = std::thread::spawn(move || {
+            Solver::try_from((Config::default(), v1.as_ref()))
+                .expect("panic")
+                .solve()
+        });
+        let h2 = std::thread::spawn(move || {
+            Solver::try_from((Config::default(), v2.as_ref()))
+                .expect("panic")
+                .solve()
+        });
+        assert_eq!(h1.join().unwrap(), Ok(Certificate::SAT(vec![1, 2])));
+        assert!(matches!(h2.join().unwrap(), Ok(Certificate::SAT(v)) if v.len() == 4));
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_interrupt() {
+        use std::sync::{atomic::AtomicBool, Arc};
+        let config = Config::from("cnfs/sample.cnf");
+        let mut s = Solver::build(&config).expect("failed to load");
+        s.state.interrupt = Some(Arc::new(AtomicBool::new(true)));
+        assert_eq!(s.solve(), Ok(Certificate::UNKNOWN));
+        assert_eq!(s.assign_value(1), None);
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_resource_budget() {
+        let mut config = Config::from("cnfs/sample.cnf");
+        config.max_conflicts = Some(10);
+        let mut s = Solver::build(&config).expect("failed to load");
+        assert_eq!(s.solve(), Ok(Certificate::UNKNOWN));
+        assert_eq!(s.asg.derefer(assign::property::Tusize::NumConflict), 10);
+        let mut config = Config::from("cnfs/sample.cnf");
+        config.max_propagations = Some(100);
+        let mut s = Solver::build(&config).expect("failed to load");
+        assert_eq!(s.solve(), Ok(Certificate::UNKNOWN));
+        assert!(100 <= s.asg.derefer(assign::property::Tusize::NumPropagation));
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_added_clause_survives_reduction() {
+        let mut config = Config::from("cnfs/sample.cnf");
+        config.enable_vivification = false;
+        config.quiet_mode = true;
+        let mut s = Solver::build(&config).expect("failed to load");
+        let lemma: [i32; 3] = [-10, 20, 30];
+        for i in lemma.iter() {
+            s.freeze(i.unsigned_abs() as VarId);
+        }
+        s.add_clause(lemma).expect("failed to add");
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        assert!(10 < s.cdb.derefer(cdb::property::Tusize::NumReduction));
+        let mut lits = lemma.iter().map(|i| Lit::from(*i)).collect::<Vec<_>>();
+        lits.sort();
+        assert!(s.cdb.iter_active().any(|(cid, c)| {
+            let mut c = c.to_vec();
+            c.sort();
+            c == lits && !s.cdb[cid].is(FlagClause::LEARNT)
+        }));
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_dump_on_timeout() {
+        let dump = std::env::temp_dir().join(format!(
+            "splr-test-dump-on-timeout-{}.cnf",
+            std::process::id()
+        ));
+        let mut config = Config::from("cnfs/sample.cnf");
+        config.max_conflicts = Some(100);
+        config.dump_on_timeout = Some(dump.clone());
+        let mut s = Solver::build(&config).expect("failed to load");
+        assert_eq!(s.solve(), Ok(Certificate::UNKNOWN));
+        let num_learnt = s.cdb.derefer(cdb::property::Tusize::NumLearnt);
+        assert!(0 < num_learnt);
+        let text = std::fs::read_to_string(&dump).expect("failed to read the dump");
+        for l in s.root_assignments() {
+            assert!(text.lines().any(|c| c == format!("{} 0", i32::from(l))));
+        }
+        let mut residual = Solver::try_from(dump.as_path()).expect("failed to load the dump");
+        assert!(num_learnt <= residual.cdb.derefer(cdb::property::Tusize::NumClause));
+        assert!(matches!(residual.solve(), Ok(Certificate::SAT(_))));
+        std::fs::remove_file(dump).expect("failed to remove the dump");
+    }
+
+    #[test]
+    fn test_add_var() {
+        let mut slv = Solver::instantiate(
+            &Config::default(),
+            &CNFDescription {
+                num_of_variables: 1,
+                ..CNFDescription::default()
+            },
+        );
+        let a = slv.add_var() as i32;
+        let b = slv.add_var() as i32;
+        let c = slv.add_var() as i32;
+        assert_eq!((a, b, c), (2, 3, 4));
+        assert!(slv.add_clause(vec![-a, -b]).is_ok());
+        assert!(slv.add_clause(vec![a, c]).is_ok());
+        assert!(slv.add_clause(vec![b, c]).is_ok());
+        assert!(slv.add_clause(vec![-c, a]).is_ok());
+        assert!(slv.add_clause(vec![-1, -c]).is_ok());
+        assert_eq!(slv.solve(), Ok(Certificate::SAT(vec![-1, 2, -3, 4])));
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_soft_limit() {
+        let mut config = Config::from("cnfs/sample.cnf");
+        config.c_cls_lim = 1165;
+        config.quiet_mode = true;
+        let mut s = Solver::build(&config).expect("failed to load");
+        let Ok(Certificate::SAT(model)) = s.solve() else {
+            panic!("not SAT");
+        };
+        check_model("cnfs/sample.cnf", &model);
+    }
+
+    /// check `model` against the clauses in `path` by another solver.
+    #[cfg(not(feature = "no_IO"))]
+    fn check_model(path: &str, model: &[i32]) {
+        let mut validator = Solver::try_from(std::path::Path::new(path)).expect("panic");
+        validator
+            .inject_assignment(model)
+            .expect("It's completely broken!");
+        if let Some(v) = validator.validate() {
+            panic!("It's an invalid assignment against clause {v:?}.");
+        }
+    }
+
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_local_minimization() {
+        for path in ["cnfs/sample.cnf", "cnfs/uf100-010.cnf"] {
+            let mut config = Config::from(path);
+            config.cls_min_rec = false;
+            config.quiet_mode = true;
+            let mut s = Solver::build(&config).expect("failed to load");
+            let Ok(Certificate::SAT(model)) = s.solve() else {
+                panic!("not SAT");
+            };
+            check_model(path, &model);
+        }
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_validate_against() {
+        let path = std::env::temp_dir().join(format!("splr-validate-{}.cnf", std::process::id()));
+        std::fs::write(&path, "p cnf 2 3\n1 2 0\n-1 2 0\n-2 0\n").expect("can't write");
+        let mut s = Solver::try_from((Config::default(), vec![vec![1, 2], vec![-1, 2]].as_ref()))
+            .expect("panic");
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        let result = s.validate_against(&path);
+        std::fs::remove_file(&path).expect("can't remove");
+        assert_eq!(result, Err(ValidationError::Falsified(3)));
+        assert_eq!(s.validate_against(&path), Err(ValidationError::IOError));
+    }
+
     macro_rules! run {
         ($vec: expr) => {
             println!(
@@ -310,6 +1331,390 @@ mod tests {
         );
         assert_eq!(slv.iter().count(), 256);
     }
+    #[cfg(all(feature = "incremental_solver", not(feature = "no_IO")))]
+    #[test]
+    fn test_solve_all_on_unknown() {
+        use std::sync::{atomic::AtomicBool, Arc};
+        let mut slv = Solver::build(&Config::from("cnfs/sample.cnf")).expect("failed to load");
+        slv.state.interrupt = Some(Arc::new(AtomicBool::new(true)));
+        assert_eq!(slv.solve_all(2), Ok(vec![]));
+        assert_eq!(slv.solve_all_projected(&[1, 2], 2), Ok(vec![]));
+    }
+    #[cfg(all(feature = "incremental_solver", not(feature = "no_IO")))]
+    #[test]
+    fn test_solver_iter_on_unknown() {
+        use std::sync::{atomic::AtomicBool, Arc};
+        let mut slv = Solver::build(&Config::from("cnfs/sample.cnf")).expect("failed to load");
+        slv.state.interrupt = Some(Arc::new(AtomicBool::new(true)));
+        assert_eq!(slv.iter().next(), None);
+    }
+    #[cfg(all(feature = "incremental_solver", not(feature = "no_IO")))]
+    #[test]
+    fn test_reset_keeps_learnts() {
+        use crate::cdb;
+        let mut slv = Solver::try_from(std::path::Path::new("cnfs/uf8.cnf")).expect("panic");
+        slv.push_assumption(Lit::from(1));
+        assert!(matches!(
+            slv.solve_under_assumptions(),
+            Ok(Certificate::SAT(v)) if v[0] == 1
+        ));
+        let num_learnt = slv.cdb.derefer(cdb::property::Tusize::NumLearnt);
+        let num_conflict = slv.asg.derefer(assign::property::Tusize::NumConflict);
+        slv.reset();
+        assert_eq!(slv.assign_value(1), None);
+        assert_eq!(
+            slv.cdb.derefer(cdb::property::Tusize::NumLearnt),
+            num_learnt
+        );
+        assert_eq!(
+            slv.asg.derefer(assign::property::Tusize::NumConflict),
+            num_conflict
+        );
+        // the assumption is retracted: the opposite one is consistent with the state.
+        assert_eq!(slv.pop_assumption(), Some(Lit::from(1)));
+        slv.push_assumption(Lit::from(-1));
+        match slv.solve_under_assumptions() {
+            Ok(Certificate::SAT(v)) => assert_eq!(v[0], -1),
+            Ok(Certificate::UNSAT) => assert_eq!(slv.state.conflicts, vec![Lit::from(-1)]),
+            e => panic!("{e:?}"),
+        }
+        assert!(slv.pop_assumption().is_some());
+        assert!(matches!(
+            slv.solve_under_assumptions(),
+            Ok(Certificate::SAT(_))
+        ));
+    }
+    #[cfg(feature = "incremental_solver")]
+    #[test]
+    fn test_solve_all() {
+        let mut slv = Solver::instantiate(
+            &Config::default(),
+            &CNFDescription {
+                num_of_variables: 4,
+                ..CNFDescription::default()
+            },
+        );
+        assert!(slv.add_clause(vec![1, 2]).is_ok());
+        let models = slv.solve_all(5).expect("panic");
+        assert_eq!(models.len(), 5);
+        assert!(models.iter().all(|m| m[0] == 1 || m[1] == 2));
+        assert_eq!(slv.solve_all(20).map(|v| v.len()), Ok(12 - 5));
+    }
+    #[test]
+    fn test_solve_trivial_formulas() {
+        let cnf = CNFDescription {
+            num_of_variables: 3,
+            ..CNFDescription::default()
+        };
+        let mut s = Solver::instantiate(&Config::default(), &cnf);
+        assert_eq!(s.solve(), Ok(Certificate::SAT(vec![-1, -2, -3])));
+        assert!(s.add_assignment(2).is_ok());
+        assert_eq!(s.solve(), Ok(Certificate::SAT(vec![-1, 2, -3])));
+        // the empty clause makes the solver unsatisfiable even if the error is ignored.
+        let mut t = s.clone();
+        assert!(t.add_clause(Vec::<i32>::new()).is_err());
+        assert_eq!(t.solve(), Ok(Certificate::UNSAT));
+        // so does a clause falsified by root-level assignments.
+        assert!(s.add_clause([-2]).is_err());
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_fork() {
+        use std::sync::{atomic::AtomicBool, Arc};
+        let config = Config {
+            quiet_mode: true,
+            ..Config::from("cnfs/uf100-010.cnf")
+        };
+        let mut s = Solver::build(&config).expect("panic");
+        s.state.interrupt = Some(Arc::new(AtomicBool::new(false)));
+        let handles = [1, -1]
+            .into_iter()
+            .map(|l| {
+                let mut t = s.fork();
+                assert!(t.state.interrupt.is_none());
+                std::thread::spawn(move || {
+                    t.add_assignment(l).expect("panic");
+                    (l, t.solve())
+                })
+            })
+            .collect::<Vec<_>>();
+        for h in handles {
+            match h.join().expect("panic") {
+                (l, Ok(Certificate::SAT(m))) => assert_eq!(m[0], l),
+                (_, result) => assert_eq!(result, Ok(Certificate::UNSAT)),
+            }
+        }
+        // the original is intact.
+        assert!(!s.is_asserted(1));
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    }
+    #[test]
+    fn test_search_phase() {
+        use crate::primitive::ema::EmaMutIF;
+        let clauses = vec![vec![1, 2], vec![-1, 3]];
+        let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+        s.state.trail_ema.reset_to(100.0);
+        s.state.trail_ema.reset_slow();
+        for _ in 0..1_000 {
+            s.state.trail_ema.update(100.0);
+        }
+        assert_eq!(s.search_phase(), SearchPhase::Undetermined);
+        s.asg.num_conflict = 5_000;
+        assert_eq!(s.search_phase(), SearchPhase::Undetermined);
+        let mut t = s.clone();
+        for _ in 0..200 {
+            s.state.trail_ema.update(150.0);
+            t.state.trail_ema.update(50.0);
+        }
+        assert_eq!(s.search_phase(), SearchPhase::SatLike);
+        assert_eq!(t.search_phase(), SearchPhase::UnsatLike);
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_minimal_model() {
+        use std::{ops::ControlFlow, path::Path};
+        for name in ["cnfs/uf100-010.cnf", "cnfs/sample.cnf"] {
+            let path = Path::new(name);
+            let mut s = Solver::try_from(path).expect("can't load");
+            let Ok(Certificate::SAT(full)) = s.solve() else {
+                panic!("{name} should be satisfiable");
+            };
+            let model = s.minimal_model();
+            assert!(model.len() < full.len());
+            assert!(model.iter().all(|l| full.contains(l)));
+            crate::cnf::for_each_clause(path, |c| {
+                assert!(c.iter().any(|l| model.contains(l)), "{c:?}");
+                ControlFlow::Continue(())
+            })
+            .expect("can't read");
+        }
+    }
+    #[test]
+    fn test_solve_under_assumptions_in_place() {
+        // 7 pigeons can't be in 6 holes unless selector 43 lets pigeon 6 fly away.
+        let var = |p: i32, h: i32| 6 * p + h + 1;
+        let mut clauses = (0..7)
+            .map(|p| (0..6).map(|h| var(p, h)).collect::<Vec<i32>>())
+            .collect::<Vec<_>>();
+        clauses[6].push(43);
+        for h in 0..6 {
+            for p in 0..7 {
+                for q in p + 1..7 {
+                    clauses.push(vec![-var(p, h), -var(q, h)]);
+                }
+            }
+        }
+        let config = Config {
+            quiet_mode: true,
+            ..Config::default()
+        };
+        let mut s = Solver::try_from((config, clauses.as_ref())).expect("panic");
+        s.push_assumption(Lit::from(-43));
+        assert_eq!(s.solve_under_assumptions(), Ok(Certificate::UNSAT));
+        assert_eq!(s.decision_level(), 0);
+        let num_conflict = s.asg.num_conflict;
+        assert!(0 < num_conflict);
+        assert!(0 < s.num_learnt());
+        assert_eq!(s.pop_assumption(), Some(Lit::from(-43)));
+        s.push_assumption(Lit::from(43));
+        s.push_assumption(Lit::from(var(0, 0)));
+        match s.solve_under_assumptions() {
+            Ok(Certificate::SAT(m)) => {
+                assert!(m[42] == 43 && m[0] == 1);
+                assert!(clauses.iter().all(|c| c.iter().any(|l| m.contains(l))));
+            }
+            e => panic!("{e:?}"),
+        }
+        assert_eq!(s.decision_level(), 0);
+        assert!(num_conflict <= s.asg.num_conflict);
+        s.state.assumptions.clear();
+        s.push_assumption(Lit::from(-43));
+        assert_eq!(s.solve_under_assumptions(), Ok(Certificate::UNSAT));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_stagnation_limit() {
+        let config = Config {
+            stagnation_limit: Some(20),
+            quiet_mode: true,
+            ..Config::from("cnfs/uf100-010.cnf")
+        };
+        let mut s = Solver::build(&config).expect("failed to load");
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        assert!(0 < s.state[Stat::StagnationReset]);
+    }
+    #[test]
+    fn test_assert_and_propagate_to_conflict() {
+        let clauses = vec![vec![-1, 2], vec![-2, 3], vec![-1, -3]];
+        let mut s = Solver::try_from((Config::default(), clauses.as_ref())).expect("panic");
+        assert_eq!(s.assert_and_propagate(&[1]), Err(SolverError::Inconsistent));
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_certificate_ends_with_empty_clause() {
+        let dir = std::env::temp_dir();
+        let pfile = format!("splr-test-{}.drat", std::process::id());
+        let config = Config {
+            use_certification: true,
+            io_odir: dir.clone(),
+            io_pfile: pfile.clone().into(),
+            quiet_mode: true,
+            ..Config::default()
+        };
+        let clauses = vec![
+            vec![1, 2],
+            vec![-1, 2],
+            vec![1, -2],
+            vec![-1, -2, 3],
+            vec![-1, -2, -3],
+        ];
+        let mut s = Solver::try_from((config.clone(), clauses.as_ref())).expect("panic");
+        assert_eq!(s.solve(), Ok(Certificate::UNSAT));
+        let path = dir.join(pfile);
+        let proof = std::fs::read_to_string(&path).expect("no certificate");
+        std::fs::remove_file(&path).expect("panic");
+        // the in-memory sink records the same certificate.
+        let config = Config {
+            proof_in_memory: true,
+            ..config
+        };
+        let mut s = Solver::try_from((config, clauses.as_ref())).expect("panic");
+        let (result, in_memory) = s.solve_with_proof();
+        assert_eq!(result, Ok(Certificate::UNSAT));
+        assert_eq!(in_memory, Some(proof.clone().into_bytes()));
+        // check each added clause by reverse unit propagation, ignoring deletions.
+        let mut db = clauses.clone();
+        for line in proof.lines() {
+            if line.starts_with('d') {
+                continue;
+            }
+            let lemma = line
+                .split_whitespace()
+                .map(|s| s.parse::<i32>().expect("panic"))
+                .take_while(|l| *l != 0)
+                .collect::<Vec<i32>>();
+            let mut assigned = lemma.iter().map(|l| -l).collect::<Vec<i32>>();
+            let mut conflict = false;
+            while !conflict {
+                let mut updated = false;
+                for c in db.iter() {
+                    if c.iter().any(|l| assigned.contains(l)) {
+                        continue;
+                    }
+                    let mut unassigned = c.iter().filter(|l| !assigned.contains(&-**l));
+                    match (unassigned.next(), unassigned.next()) {
+                        (None, _) => conflict = true,
+                        (Some(l), None) => {
+                            assigned.push(*l);
+                            updated = true;
+                        }
+                        _ => continue,
+                    }
+                    break;
+                }
+                if !updated {
+                    break;
+                }
+            }
+            assert!(conflict, "not RUP: {line}");
+            db.push(lemma);
+        }
+        assert_eq!(proof.lines().last(), Some("0"));
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_proof_without_deletions() {
+        // the pigeonhole problem: 7 pigeons can't be in 6 holes. Var `6p + h + 1` means
+        // pigeon p is in hole h.
+        let var = |p: i32, h: i32| 6 * p + h + 1;
+        let mut clauses = (0..7)
+            .map(|p| (0..6).map(|h| var(p, h)).collect::<Vec<i32>>())
+            .collect::<Vec<_>>();
+        for h in 0..6 {
+            for p in 0..7 {
+                for q in p + 1..7 {
+                    clauses.push(vec![-var(p, h), -var(q, h)]);
+                }
+            }
+        }
+        let proof_of = |proof_emit_deletions: bool| -> String {
+            let config = Config {
+                use_certification: true,
+                proof_in_memory: true,
+                proof_emit_deletions,
+                first_reduction: 20,
+                reduction_increment: 10,
+                quiet_mode: true,
+                ..Config::default()
+            };
+            let mut s = Solver::try_from((config, clauses.as_ref())).expect("panic");
+            let (result, proof) = s.solve_with_proof();
+            assert_eq!(result, Ok(Certificate::UNSAT));
+            String::from_utf8(proof.expect("no proof")).expect("panic")
+        };
+        let full = proof_of(true);
+        let additions = proof_of(false);
+        assert!(full.lines().any(|l| l.starts_with('d')));
+        assert!(!additions.lines().any(|l| l.starts_with('d')));
+        assert_eq!(
+            additions.lines().collect::<Vec<_>>(),
+            full.lines()
+                .filter(|l| !l.starts_with('d'))
+                .collect::<Vec<_>>()
+        );
+    }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_scheduled_reduction() {
+        for (first, reduced) in [(0, true), (20, true), (usize::MAX / 2, false)] {
+            let config = Config {
+                first_reduction: first,
+                reduction_increment: 10,
+                quiet_mode: true,
+                ..Config::from("cnfs/uf100-010.cnf")
+            };
+            let mut s = Solver::build(&config).expect("panic");
+            assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+            let num_reduction = s.cdb.derefer(cdb::property::Tusize::NumReduction);
+            assert_eq!(0 < num_reduction, reduced);
+        }
+    }
+    #[test]
+    fn test_self_check() {
+        let config = Config {
+            self_check: true,
+            ..Config::default()
+        };
+        let clauses = vec![vec![1, 2], vec![-1, 2], vec![2, 3]];
+        let mut s = Solver::try_from((config, clauses.as_ref())).expect("panic");
+        assert_eq!(s.state.original_clauses.len(), 3);
+        assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+        // simulate a model broken by the solver
+        s.state.original_clauses.push(vec![Lit::from(-2)]);
+        assert_eq!(s.solve(), Err(SolverError::SolverBug));
+    }
+    #[cfg(feature = "incremental_solver")]
+    #[test]
+    fn test_solve_all_projected() {
+        let mut slv = Solver::instantiate(
+            &Config::default(),
+            &CNFDescription {
+                num_of_variables: 4,
+                ..CNFDescription::default()
+            },
+        );
+        assert!(slv.add_clause(vec![1, 2]).is_ok());
+        assert!(slv.add_clause(vec![-2, 3, 4]).is_ok());
+        assert!(matches!(
+            slv.solve_all_projected(&[5], 10),
+            Err(SolverError::InvalidLiteral)
+        ));
+        let mut models = slv.solve_all_projected(&[1, 2], 10).expect("panic");
+        models.sort();
+        assert_eq!(models, vec![vec![-1, 2], vec![1, -2], vec![1, 2]]);
+    }
     #[cfg(feature = "incremental_solver")]
     #[test]
     fn test_add_var_on_incremental_solver() {
@@ -353,7 +1758,7 @@ mod tests {
         let mut slv = Solver::instantiate(
             &Config::default(),
             &CNFDescription {
-                num_of_variables: 3 as usize,
+                num_of_variables: 3,
                 ..CNFDescription::default()
             },
         );
@@ -0,0 +1,101 @@
+//! Structural statistics of a problem
+use {
+    super::Solver,
+    crate::{
+        assign::{AssignIF, VarManipulateIF},
+        types::*,
+    },
+};
+
+/// Cheap structural statistics on the clauses stored in a solver.
+/// Learnt clauses are excluded. Unit clauses are not stored in `ClauseDB`; they are
+/// counted by `num_unit` only.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProblemProfile {
+    /// the number of vars
+    pub num_var: usize,
+    /// the number of vars assigned at the root level
+    pub num_unit: usize,
+    /// the number of stored (non-unit) clauses
+    pub num_clause: usize,
+    /// the number of binary clauses
+    pub num_bi_clause: usize,
+    /// the number of ternary clauses
+    pub num_tri_clause: usize,
+    /// the number of clauses having at most one positive literal
+    pub num_horn: usize,
+    /// the number of clauses having exactly one positive literal
+    pub num_definite: usize,
+    /// the average length of stored clauses
+    pub average_clause_len: f64,
+    /// `var_occurrence[k]` is the number of vars occurring in `k` clauses
+    pub var_occurrence: Vec<usize>,
+}
+
+impl Solver {
+    /// return the structural statistics of the problem, which are useful for instance
+    /// characterization. Call this before `solve` to get them on the original problem.
+    ///
+    /// # Example
+    /// ```
+    /// use crate::splr::*;
+    ///
+    /// let s = Solver::try_from((
+    ///     Config::default(),
+    ///     vec![vec![1, 2], vec![-1, -2, 3], vec![-2, -3], vec![4]].as_ref(),
+    /// ))
+    /// .expect("panic");
+    /// let p = s.problem_profile();
+    /// assert_eq!((p.num_var, p.num_unit, p.num_clause), (4, 1, 3));
+    /// assert_eq!((p.num_bi_clause, p.num_tri_clause), (2, 1));
+    /// assert_eq!((p.num_horn, p.num_definite), (2, 1));
+    /// assert_eq!(p.average_clause_len, 7.0 / 3.0);
+    /// assert_eq!(p.var_occurrence, vec![1, 0, 2, 1]);
+    /// ```
+    pub fn problem_profile(&self) -> ProblemProfile {
+        let Solver { asg, cdb, .. } = self;
+        let mut profile = ProblemProfile {
+            num_var: asg.num_vars,
+            num_unit: asg
+                .stack_iter()
+                .take_while(|l| asg.level(l.vi()) == 0)
+                .count(),
+            ..ProblemProfile::default()
+        };
+        let mut occurrence = vec![0; asg.num_vars + 1];
+        let mut num_lit = 0;
+        for (cid, lits) in cdb.iter_active() {
+            if cdb[cid].is(FlagClause::LEARNT) {
+                continue;
+            }
+            profile.num_clause += 1;
+            num_lit += lits.len();
+            match lits.len() {
+                2 => profile.num_bi_clause += 1,
+                3 => profile.num_tri_clause += 1,
+                _ => (),
+            }
+            match lits.iter().filter(|l| bool::from(**l)).count() {
+                0 => profile.num_horn += 1,
+                1 => {
+                    profile.num_horn += 1;
+                    profile.num_definite += 1;
+                }
+                _ => (),
+            }
+            for l in lits.iter() {
+                occurrence[l.vi()] += 1;
+            }
+        }
+        if 0 < profile.num_clause {
+            profile.average_clause_len = num_lit as f64 / profile.num_clause as f64;
+        }
+        let max_occurrence = occurrence.iter().skip(1).max().copied().unwrap_or(0);
+        profile.var_occurrence = vec![0; max_occurrence + 1];
+        for n in occurrence.iter().skip(1) {
+            profile.var_occurrence[*n] += 1;
+        }
+        profile
+    }
+}
@@ -1,10 +1,19 @@
 //! Module `restart` provides restart heuristics.
-use crate::types::*;
+use {
+    crate::types::*,
+    std::{
+        fmt,
+        sync::{Arc, Mutex},
+    },
+};
 
 /// API for [`restart`](`crate::solver::RestartIF::restart`)
 pub trait RestartIF: Instantiate {
     /// check blocking and forcing restart condition.
     fn restart(&mut self, ldb: &EmaView, ent: &EmaView) -> bool;
+    /// record the number of assigned vars at a conflict, used for blocking restart.
+    /// This is called at every conflict only if `Config::restart_blocking_factor` is positive.
+    fn update_trail(&mut self, len: usize);
     /// set stabilization parameters
     fn set_stage_parameters(&mut self, step: usize);
     /// adjust restart threshold
@@ -13,38 +22,110 @@ pub trait RestartIF: Instantiate {
 
 const FUEL: f64 = 2.0;
 const SCALE: f64 = 64.0;
+/// the window of the trail size EMA; blocking is suppressed until it is filled.
+const TRAIL_EMA_LEN: usize = 5_000;
+
+/// A callback invoked on every restart with the number of restarts and the number of
+/// conflicts since the previous restart.
+#[derive(Clone)]
+pub struct RestartCallback(pub Arc<Mutex<dyn FnMut(usize, u32) + Send>>);
+
+impl fmt::Debug for RestartCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RestartCallback")
+    }
+}
 
 /// `RestartManager` provides restart API and holds data about restart conditions.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct RestartManager {
     penetration_energy: f64,
     pub penetration_energy_charged: f64,
     penetration_energy_unit: f64,
     field_scale: f64,
+    /// restart is blocked if the trail is longer than this factor times its average.
+    blocking_factor: f64,
+    trail_ema: Ema,
+    trail_len: usize,
+    num_trail_update: usize,
+    /// the number of restarts and conflicts since the last restart, for `callback`.
+    num_restart: usize,
+    num_conflict: usize,
+    pub callback: Option<RestartCallback>,
+}
+
+impl Default for RestartManager {
+    fn default() -> Self {
+        RestartManager {
+            penetration_energy: 0.0,
+            penetration_energy_charged: 0.0,
+            penetration_energy_unit: 0.0,
+            field_scale: 0.0,
+            blocking_factor: 0.0,
+            trail_ema: Ema::new(TRAIL_EMA_LEN),
+            trail_len: 0,
+            num_trail_update: 0,
+            num_restart: 0,
+            num_conflict: 0,
+            callback: None,
+        }
+    }
 }
 
 impl Instantiate for RestartManager {
-    fn instantiate(_config: &Config, _cnf: &CNFDescription) -> Self {
+    fn instantiate(config: &Config, _cnf: &CNFDescription) -> Self {
         RestartManager {
             penetration_energy: FUEL,
             penetration_energy_charged: FUEL,
             penetration_energy_unit: FUEL,
             field_scale: 1.0 / SCALE,
+            blocking_factor: config.restart_blocking_factor,
+            ..RestartManager::default()
         }
     }
     fn handle(&mut self, e: SolverEvent) {
-        if e == SolverEvent::Restart {
+        if e == SolverEvent::Conflict {
+            self.num_conflict += 1;
+        } else if e == SolverEvent::Restart {
             self.penetration_energy = self.penetration_energy_charged;
+            self.num_restart += 1;
+            if let Some(RestartCallback(ref f)) = self.callback {
+                if let Ok(mut f) = f.lock() {
+                    f(self.num_restart, self.num_conflict as u32);
+                }
+            }
+            self.num_conflict = 0;
         }
     }
 }
 
+impl RestartManager {
+    /// return the number of conflicts since the last restart.
+    pub fn conflicts_since_restart(&self) -> usize {
+        self.num_conflict
+    }
+}
+
 impl RestartIF for RestartManager {
     fn restart(&mut self, lbd: &EmaView, ent: &EmaView) -> bool {
         let gscale = |x: f64| self.field_scale * (x - 1.0) + 1.0;
         self.penetration_energy -= (lbd.trend() + gscale(ent.trend())) - 2.0;
+        if self.penetration_energy < 0.0
+            && 0.0 < self.blocking_factor
+            && TRAIL_EMA_LEN <= self.num_trail_update
+            && self.blocking_factor * self.trail_ema.get() < self.trail_len as f64
+        {
+            // Glucose-style blocking: we may be close to a model.
+            self.penetration_energy = self.penetration_energy_charged;
+            return false;
+        }
         self.penetration_energy < 0.0
     }
+    fn update_trail(&mut self, len: usize) {
+        self.trail_len = len;
+        self.trail_ema.update(len as f64);
+        self.num_trail_update += 1;
+    }
     fn set_segment_parameters(&mut self, segment_scale: usize) {
         let factor = 0.5 * (segment_scale.trailing_zeros() + 1) as f64;
         self.field_scale = 1.0 / (SCALE - factor);
@@ -56,3 +137,25 @@ impl RestartIF for RestartManager {
         self.penetration_energy = e;
     }
 }
+
+#[cfg(all(test, not(feature = "no_IO")))]
+mod tests {
+    use crate::{solver::SatSolverIF, *};
+
+    #[test]
+    fn test_update_trail_only_if_blocking() {
+        for factor in [0.0, 1.4] {
+            let config = Config {
+                restart_blocking_factor: factor,
+                quiet_mode: true,
+                ..Config::from("cnfs/uf100-010.cnf")
+            };
+            let mut s = Solver::build(&config).expect("failed to load");
+            assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+            let num_conflict = s.asg.num_conflict;
+            assert!(0 < num_conflict);
+            let expected = if factor == 0.0 { 0 } else { num_conflict };
+            assert_eq!(s.state.restart.num_trail_update, expected);
+        }
+    }
+}
@@ -2,17 +2,21 @@
 use {
     super::{
         conflict::handle_conflict, restart::RestartIF, Certificate, Solver, SolverEvent,
-        SolverResult,
+        SolverResult, TraceEvent,
     },
     crate::{
         assign::{self, AssignIF, AssignStack, PropagateIF, VarManipulateIF, VarSelectIF},
         cdb::{self, ClauseDB, ClauseDBIF, ReductionType, VivifyIF},
+        config::InitialPolarity,
         processor::{EliminateIF, Eliminator},
         state::{Stat, State, StateIF},
         types::*,
     },
 };
 
+#[cfg(feature = "trail_saving")]
+use crate::assign::TrailSavingIF;
+
 /// API to [`solve`](`crate::solver::SolveIF::solve`) SAT problems.
 pub trait SolveIF {
     /// search an assignment.
@@ -45,14 +49,71 @@ impl SolveIF for Solver {
     /// }
     ///```
     fn solve(&mut self) -> SolverResult {
+        let answer = self.solve_formula(&[]);
+        // The certificate ends with the empty clause, which `close` writes.
+        if answer == Ok(Certificate::UNSAT) {
+            self.cdb.certificate_save();
+        }
+        answer
+    }
+}
+
+impl Solver {
+    /// search a model in which all `assumptions` hold, which are decided before any other
+    /// var. `Ok(Certificate::UNSAT)` means that the formula or `assumptions` are conflicting.
+    pub(super) fn solve_formula(&mut self, assumptions: &[Lit]) -> SolverResult {
         let Solver {
             ref mut asg,
             ref mut cdb,
             ref mut state,
         } = self;
+        state.model = None;
+        state.trace.clear();
+        state.conflicts.clear();
         if cdb.check_size().is_err() {
             return Err(SolverError::OutOfMemory);
         }
+        if state.inconsistent {
+            return Ok(Certificate::UNSAT);
+        }
+        // A formula without clauses is satisfied by the root-level assignments.
+        if cdb.derefer(cdb::property::Tusize::NumClause) == 0
+            && asg.eliminated.is_empty()
+            && cdb.eliminated_permanent.is_empty()
+        {
+            let mut model = asg.assign_ref().to_vec();
+            for l in assumptions.iter() {
+                match model[l.vi()] {
+                    None => model[l.vi()] = Some(bool::from(*l)),
+                    Some(b) if b != bool::from(*l) => {
+                        // `l` is falsified at the root level or by the assumption `!l`.
+                        state.conflicts.clear();
+                        state.conflicts.push(*l);
+                        if asg.assigned(*l).is_none() {
+                            state.conflicts.push(!*l);
+                        }
+                        return Ok(Certificate::UNSAT);
+                    }
+                    _ => (),
+                }
+            }
+            let model = model
+                .iter()
+                .map(|a| Some(a.unwrap_or(false)))
+                .collect::<Vec<_>>();
+            if state.check_original_clauses(&model).is_some() {
+                state.log(
+                    None,
+                    "failed to validate the model against the given clauses",
+                );
+                return Err(SolverError::SolverBug);
+            }
+            let vals = (1..=asg.num_vars)
+                .map(|vi| i32::from(Lit::from((vi, model[vi].unwrap()))))
+                .collect::<Vec<i32>>();
+            state.model = Some(vals.clone());
+            return Ok(Certificate::SAT(state.order_model(vals)));
+        }
         #[cfg(feature = "incremental_solver")]
         {
             // Reinitialize AssignStack::var_order with respect for assignments.
@@ -64,12 +125,9 @@ impl SolveIF for Solver {
         state.flush("Preprocessing stage: ");
 
         #[cfg(feature = "clause_vivification")]
-        {
+        if state.config.enable_vivification {
             state.flush("vivifying...");
             if cdb.vivify(asg, state).is_err() {
-                #[cfg(feature = "support_user_assumption")]
-                analyze_final(asg, state, &cdb[ci]);
-
                 state.log(None, "By vivifier as a pre-possessor");
                 return Ok(Certificate::UNSAT);
             }
@@ -101,7 +159,8 @@ impl SolveIF for Solver {
                 state.flush("phasing...");
                 elim.prepare(asg, cdb, true);
                 for vi in 1..=asg.num_vars {
-                    if asg.assign(vi).is_some() {
+                    // vars may have been eliminated by `Solver::preprocess`.
+                    if asg.assign(vi).is_some() || asg.var(vi).is(FlagVar::ELIMINATED) {
                         continue;
                     }
                     if let Some((p, m)) = elim.stats(vi) {
@@ -156,10 +215,13 @@ impl SolveIF for Solver {
                             _ => (),
                         }
                     }
-                    let act = 1.0 / (asg.num_vars as f64).powf(0.25);
-                    for vi in 1..asg.num_vars {
-                        if !asg.var(vi).is(FlagVar::ELIMINATED) {
-                            asg.set_activity(vi, act);
+                    // keep activities given by `Solver::import_activities` or `Solver::set_initial_order`
+                    if (1..=asg.num_vars).all(|vi| asg.activity(vi) == 0.0) {
+                        let act = 1.0 / (asg.num_vars as f64).powf(0.25);
+                        for vi in 1..asg.num_vars {
+                            if !asg.var(vi).is(FlagVar::ELIMINATED) {
+                                asg.set_activity(vi, act);
+                            }
                         }
                     }
                     asg.rebuild_order();
@@ -169,11 +231,16 @@ impl SolveIF for Solver {
             state[Stat::Simplify] += 1;
             state[Stat::SubsumedClause] = elim.num_subsumed;
         }
+        // The preprocessor has set phases by occurrences; override them before the first search.
+        if asg.num_conflict == 0 && state.config.initial_polarity != InitialPolarity::OccurrenceBias
+        {
+            set_initial_polarity(asg, cdb, state.config.initial_polarity);
+        }
         //
         //## Search
         //
         state.progress(asg, cdb);
-        let answer = search(asg, cdb, state);
+        let answer = search(asg, cdb, state, assumptions);
         state.progress(asg, cdb);
         match answer {
             Ok(true) => {
@@ -197,6 +264,14 @@ impl SolveIF for Solver {
                     state.progress(asg, cdb);
                     return Err(SolverError::SolverBug);
                 }
+                if state.check_original_clauses(&model).is_some() {
+                    state.log(
+                        None,
+                        "failed to validate the model against the given clauses",
+                    );
+                    state.progress(asg, cdb);
+                    return Err(SolverError::SolverBug);
+                }
 
                 // map `Option<bool>` to `i32`, and remove the dummy var at the head.
                 let vals = asg
@@ -213,15 +288,32 @@ impl SolveIF for Solver {
                     }
                 }
                 RESTART!(asg, cdb, state);
-                Ok(Certificate::SAT(vals))
+                state.model = Some(vals.clone());
+                Ok(Certificate::SAT(state.order_model(vals)))
             }
             Ok(false) | Err(SolverError::EmptyClause | SolverError::RootLevelConflict(_)) => {
-                #[cfg(feature = "support_user_assumption")]
-                analyze_final(asg, state, &cdb[ci]);
-
                 RESTART!(asg, cdb, state);
                 Ok(Certificate::UNSAT)
             }
+            Err(SolverError::TimeOut) => {
+                RESTART!(asg, cdb, state);
+                #[cfg(not(feature = "no_IO"))]
+                if let Some(ref fname) = state.config.dump_on_timeout {
+                    use std::io::Write;
+                    if std::fs::File::create(fname)
+                        .and_then(|f| {
+                            let mut buf = std::io::BufWriter::new(f);
+                            super::build::write_residual(asg, cdb, &mut buf, true)?;
+                            buf.flush()
+                        })
+                        .is_err()
+                    {
+                        state.log(None, "failed to dump the residual formula");
+                    }
+                }
+                state.progress(asg, cdb);
+                Ok(Certificate::UNKNOWN)
+            }
             Err(e) => {
                 RESTART!(asg, cdb, state);
                 state.progress(asg, cdb);
@@ -231,38 +323,208 @@ impl SolveIF for Solver {
     }
 }
 
-/// main loop; returns `Ok(true)` for SAT, `Ok(false)` for UNSAT.
+/// try to get back under the soft limit of clause database by aggressive reductions,
+/// each of which drops the worse half of learnt clauses by LBD.
+/// It returns `Err(SolverError::OutOfMemory)` only if no learnt clause can be removed.
+/// *precondition*: decision level == 0.
+fn reduce_on_memory_pressure(
+    asg: &mut AssignStack,
+    cdb: &mut ClauseDB,
+    state: &mut State,
+) -> MaybeInconsistent {
+    while cdb.check_size().is_err() {
+        let num_learnt = cdb.derefer(cdb::property::Tusize::NumLearnt);
+        cdb.reduce(asg, ReductionType::LBDonADD((num_learnt + 1) / 2));
+        let remain = cdb.derefer(cdb::property::Tusize::NumLearnt);
+        if remain == num_learnt {
+            return Err(SolverError::OutOfMemory);
+        }
+        state.log(None, format!("memory pressure: {remain} learnts remain"));
+    }
+    Ok(())
+}
+
+/// halve learnt clauses by LBD if the point scheduled by `Config::first_reduction` has come,
+/// then schedule the next one. `schedule` holds the point and the current interval.
+/// *precondition*: decision level == root level.
+fn reduce_on_schedule(
+    asg: &mut AssignStack,
+    cdb: &mut ClauseDB,
+    state: &State,
+    schedule: &mut Option<(usize, usize)>,
+) -> MaybeInconsistent {
+    let Some((next, interval)) = schedule else {
+        return Ok(());
+    };
+    if asg.num_conflict < *next {
+        return Ok(());
+    }
+    // no learnt clause should be a reason of root-level assignments.
+    asg.clear_asserted_literals(cdb)?;
+    // nor of the saved trail, which is reused by the next propagation.
+    #[cfg(feature = "trail_saving")]
+    asg.clear_saved_trail();
+    let num_learnt = cdb.derefer(cdb::property::Tusize::NumLearnt);
+    cdb.reduce(asg, ReductionType::LBDonADD(num_learnt / 2));
+    *interval += state.config.reduction_increment;
+    *next = asg.num_conflict + *interval;
+    Ok(())
+}
+
+/// set the phases of unassigned vars according to `policy`.
+fn set_initial_polarity(asg: &mut AssignStack, cdb: &ClauseDB, policy: InitialPolarity) {
+    let phases: Vec<bool> = match policy {
+        InitialPolarity::OccurrenceBias => return,
+        InitialPolarity::True => vec![true; asg.num_vars + 1],
+        InitialPolarity::False => vec![false; asg.num_vars + 1],
+        InitialPolarity::Random(seed) => {
+            // xorshift64, which needs a non-zero state
+            let mut s = seed.max(1);
+            (0..=asg.num_vars)
+                .map(|_| {
+                    s ^= s << 13;
+                    s ^= s >> 7;
+                    s ^= s << 17;
+                    s & 1 == 1
+                })
+                .collect()
+        }
+        InitialPolarity::Jeroslow => {
+            let mut score = vec![(0.0, 0.0); asg.num_vars + 1];
+            for (_, lits) in cdb.iter_active() {
+                let w = 0.5_f64.powi(lits.len().min(64) as i32);
+                for l in lits {
+                    if bool::from(*l) {
+                        score[l.vi()].0 += w;
+                    } else {
+                        score[l.vi()].1 += w;
+                    }
+                }
+            }
+            score.iter().map(|(p, m)| m < p).collect()
+        }
+    };
+    for (vi, phase) in phases.iter().enumerate().skip(1) {
+        if asg.assign(vi).is_none() && !asg.var(vi).is(FlagVar::ELIMINATED) {
+            asg.var_mut(vi).set(FlagVar::PHASE, *phase);
+        }
+    }
+}
+
+/// return the first unassigned literal in `assumptions`, or `Err(l)` if `l` in them is
+/// falsified. Since they are decided before other vars, `l` is falsified by them.
+fn pick_assumption(asg: &AssignStack, assumptions: &[Lit]) -> Result<Option<Lit>, Lit> {
+    for l in assumptions.iter() {
+        match asg.assigned(*l) {
+            Some(true) => (),
+            Some(false) => return Err(*l),
+            None => return Ok(Some(*l)),
+        }
+    }
+    Ok(None)
+}
+
+/// main loop; returns `Ok(true)` for SAT, `Ok(false)` for UNSAT under `assumptions`.
 fn search(
     asg: &mut AssignStack,
     cdb: &mut ClauseDB,
     state: &mut State,
+    assumptions: &[Lit],
 ) -> Result<bool, SolverError> {
     let mut previous_stage: Option<bool> = Some(true);
     let mut num_learnt = 0;
     let mut current_core: usize = 999_999;
     let mut core_was_rebuilt: Option<usize> = None;
     let stage_size: usize = 32;
+    let conflict_limit = state
+        .config
+        .max_conflicts
+        .map(|k| asg.derefer(assign::property::Tusize::NumConflict) + k);
+    let propagation_limit = state
+        .config
+        .max_propagations
+        .map(|k| asg.derefer(assign::property::Tusize::NumPropagation) + k);
+    let mut reduction_schedule = (0 < state.config.first_reduction).then(|| {
+        (
+            asg.num_conflict + state.config.first_reduction,
+            state.config.first_reduction,
+        )
+    });
     #[cfg(feature = "rephase")]
     let mut sls_core = cdb.derefer(cdb::property::Tusize::NumClause);
+    // the number of conflicts at the last new best assignment
+    let mut best_found_at = asg.num_conflict;
 
     state.stm.initialize(stage_size);
     while 0 < asg.derefer(assign::property::Tusize::NumUnassignedVar) || asg.remains() {
         if !asg.remains() {
-            let lit = asg.select_decision_literal();
+            let lit = match pick_assumption(asg, assumptions) {
+                Ok(Some(l)) => l,
+                Ok(None) => asg.select_decision_literal(),
+                Err(l) => {
+                    analyze_final(asg, cdb, state, l);
+                    return Ok(false);
+                }
+            };
             asg.assign_by_decision(lit);
+            if state.config.record_trace {
+                state.trace.push(TraceEvent::Decide(lit));
+            }
+        }
+        let propagated = asg.propagate(cdb);
+        if propagation_limit.map_or(false, |l| {
+            l <= asg.derefer(assign::property::Tusize::NumPropagation)
+        }) {
+            return Err(SolverError::TimeOut);
         }
-        let Err(cc) = asg.propagate(cdb) else {
+        let Err(cc) = propagated else {
             continue;
         };
+        if state.config.record_trace {
+            state
+                .trace
+                .push(TraceEvent::Conflict(conflicting_clause(cdb, &cc)));
+        }
         if asg.decision_level() == asg.root_level() {
             return Err(SolverError::RootLevelConflict(cc));
         }
         asg.update_activity_tick();
         #[cfg(feature = "clause_rewarding")]
         cdb.update_activity_tick();
+        state.restart.handle(SolverEvent::Conflict);
+        if 0.0 < state.config.restart_blocking_factor {
+            state.restart.update_trail(asg.stack_len());
+        }
+        if state.trail_ema.get_slow() == 0.0 {
+            // start from the first sample instead of zero
+            state.trail_ema.reset_to(asg.stack_len() as f64);
+            state.trail_ema.reset_slow();
+        } else {
+            state.trail_ema.update(asg.stack_len() as f64);
+        }
         if 1 < handle_conflict(asg, cdb, state, &cc)? {
             num_learnt += 1;
         }
+        if state.config.record_trace {
+            state
+                .trace
+                .push(TraceEvent::Backtrack(asg.decision_level()));
+        }
+        if conflict_limit.map_or(false, |l| l <= asg.num_conflict) {
+            return Err(SolverError::TimeOut);
+        }
+        if let Some(limit) = state.config.stagnation_limit {
+            if best_found_at + limit <= asg.num_conflict {
+                asg.reset_phases();
+                state[Stat::StagnationReset] += 1;
+                best_found_at = asg.num_conflict;
+            }
+        }
+        if let Some(ref flag) = state.interrupt {
+            if flag.load(std::sync::atomic::Ordering::Relaxed) {
+                return Err(SolverError::TimeOut);
+            }
+        }
         if state.stm.stage_ended(num_learnt) {
             if let Some(p) = state.elapsed() {
                 if 1.0 <= p {
@@ -272,8 +534,14 @@ fn search(
                 return Err(SolverError::UndescribedError);
             }
             RESTART!(asg, cdb, state);
+            if state.config.record_trace {
+                state.trace.push(TraceEvent::Restart);
+            }
+            #[cfg(feature = "rephase")]
             asg.select_rephasing_target();
             asg.clear_asserted_literals(cdb)?;
+            reduce_on_memory_pressure(asg, cdb, state)?;
+            reduce_on_schedule(asg, cdb, state, &mut reduction_schedule)?;
 
             #[cfg(feature = "trace_equivalency")]
             cdb.check_consistency(asg, "before simplify");
@@ -291,7 +559,7 @@ fn search(
                 // a beginning of a new cycle
                 {
                     state.exploration_rate_ema.update(1.0);
-                    if cfg!(feature = "two_mode_reduction") {
+                    if cfg!(feature = "two_mode_reduction") && reduction_schedule.is_none() {
                         cdb.reduce(
                             asg,
                             ReductionType::LBDonALL(
@@ -355,7 +623,7 @@ fn search(
                     }
                     asg.select_rephasing_target();
                 }
-                if cfg!(feature = "clause_vivification") {
+                if cfg!(feature = "clause_vivification") && state.config.enable_vivification {
                     cdb.vivify(asg, state)?;
                 }
                 if new_segment {
@@ -367,7 +635,12 @@ fn search(
                     if !cfg!(feature = "no_clause_elimination") {
                         let mut elim = Eliminator::instantiate(&state.config, &state.cnf);
                         state.flush("clause subsumption, ");
-                        elim.simplify(asg, cdb, state, false)?;
+                        match elim.simplify(asg, cdb, state, false) {
+                            Err(SolverError::OutOfMemory) => {
+                                reduce_on_memory_pressure(asg, cdb, state)?
+                            }
+                            e => e?,
+                        }
                         asg.eliminated.append(elim.eliminated_lits());
                         state[Stat::Simplify] += 1;
                         state[Stat::SubsumedClause] = elim.num_subsumed;
@@ -378,7 +651,7 @@ fn search(
                 }
             } else {
                 {
-                    if cfg!(feature = "two_mode_reduction") {
+                    if cfg!(feature = "two_mode_reduction") && reduction_schedule.is_none() {
                         cdb.reduce(
                             asg,
                             ReductionType::RASonADD(
@@ -389,7 +662,7 @@ fn search(
                 }
             }
             {
-                if !cfg!(feature = "two_mode_reduction") {
+                if !cfg!(feature = "two_mode_reduction") && reduction_schedule.is_none() {
                     cdb.reduce(
                         asg,
                         ReductionType::RASonADD(state.stm.num_reducible(state.config.cls_rdc_rm1)),
@@ -405,12 +678,21 @@ fn search(
             cdb.refer(cdb::property::TEma::Entanglement),
         ) {
             RESTART!(asg, cdb, state);
+            if state.config.record_trace {
+                state.trace.push(TraceEvent::Restart);
+            }
+            reduce_on_schedule(asg, cdb, state, &mut reduction_schedule)?;
         }
         if let Some(na) = asg.best_assigned() {
             if current_core < na && core_was_rebuilt.is_none() {
                 core_was_rebuilt = Some(current_core);
             }
             current_core = na;
+            best_found_at = asg.num_conflict;
+            #[cfg(feature = "best_phases_tracking")]
+            if state.config.core_bump {
+                asg.reward_core_vars();
+            }
             state.flush("");
             state.flush(format!("unreachable core: {na} "));
         }
@@ -426,7 +708,14 @@ fn search(
             asg.stack_len(),
         ),
     );
-    Ok(true)
+    // the last propagation may have falsified an assumption.
+    match pick_assumption(asg, assumptions) {
+        Ok(_) => Ok(true),
+        Err(l) => {
+            analyze_final(asg, cdb, state, l);
+            Ok(false)
+        }
+    }
 }
 
 /// display the current stats. before updating stabiliation parameters
@@ -529,38 +818,56 @@ fn check(asg: &mut AssignStack, cdb: &mut ClauseDB, all: bool, message: &str) {
     }
 }
 
-#[cfg(feature = "support_user_assumption")]
-// Build a conflict clause caused by *assumed* literals UNDER ROOT_LEVEL.
-// So we use zero instead of root_level sometimes in this function.
-fn analyze_final(asg: &mut AssignStack, state: &mut State, c: &Clause) {
-    let mut seen = vec![false; asg.num_vars + 1];
+/// return the clause falsified in a conflict, or `ClauseId::default()` if it isn't found,
+/// which happens with the reasons shortened by feature 'suppress_reason_chain'.
+fn conflicting_clause(cdb: &ClauseDB, cc: &ConflictContext) -> ClauseId {
+    match cc.1 {
+        AssignReason::Implication(cid) => cid,
+        AssignReason::BinaryLink(l) => cdb
+            .binary_links(!l)
+            .iter()
+            .find(|(b, _)| *b == cc.0)
+            .map_or_else(ClauseId::default, |(_, cid)| *cid),
+        _ => ClauseId::default(),
+    }
+}
+
+/// collect the assumptions responsible for `failed` being falsified into `state.conflicts`.
+/// Since assumptions are decided before other vars, the decisions in the implication
+/// graph of `!failed` are assumptions. `failed` itself is included.
+fn analyze_final(asg: &AssignStack, cdb: &ClauseDB, state: &mut State, failed: Lit) {
     state.conflicts.clear();
-    if asg.decision_level() == 0 {
+    state.conflicts.push(failed);
+    let root_level = asg.root_level();
+    if asg.level(failed.vi()) == root_level {
         return;
     }
-    // ??
-    // for l in &c.lits {
-    //     let vi = l.vi();
-    //     if asg.root_level < asg.level(vi) {
-    //         asg.var_mut(vi).turn_on(Flag::CA_SEEN);
-    //     }
-    // }
-    // FIXME: asg.stack_range().rev() is correct.
-    for l in asg.stack_range(0..asg.len_upto(asg.root_level)) {
+    let mut seen = vec![false; asg.num_vars + 1];
+    seen[failed.vi()] = true;
+    for l in asg
+        .stack_range(asg.len_upto(root_level)..asg.stack_len())
+        .iter()
+        .rev()
+    {
         let vi = l.vi();
-        if seen[vi] {
-            if let AssignReason::Decision(_) = asg.reason(vi) {
-                state.conflicts.push(!*l);
-            } else {
-                let level = asg.level_ref();
-                for l in &c[(c.len() != 2) as usize..] {
-                    let vj = l.vi();
-                    if 0 < level[vj] {
-                        seen[vj] = true;
+        if !seen[vi] {
+            continue;
+        }
+        match asg.reason(vi) {
+            AssignReason::Decision(_) => state.conflicts.push(*l),
+            AssignReason::BinaryLink(r) => {
+                if root_level < asg.level(r.vi()) {
+                    seen[r.vi()] = true;
+                }
+            }
+            AssignReason::Implication(cid) => {
+                for r in cdb[cid].iter().filter(|r| r.vi() != vi) {
+                    if root_level < asg.level(r.vi()) {
+                        seen[r.vi()] = true;
                     }
                 }
             }
+            AssignReason::None => (),
         }
-        seen[vi] = false;
     }
 }
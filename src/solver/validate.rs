@@ -6,6 +6,23 @@ use crate::{
     types::{Lit, MaybeInconsistent, SolverError},
 };
 
+#[cfg(not(feature = "no_IO"))]
+use {
+    crate::cnf::for_each_clause,
+    std::{ops::ControlFlow, path::Path},
+};
+
+/// The reasons why [`validate_against`](`crate::solver::ValidateIF::validate_against`) fails.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValidationError {
+    /// `solve` hasn't returned a model.
+    NoModel,
+    /// the CNF file can't be read or parsed.
+    IOError,
+    /// the `n`-th clause (1-based) in the file is falsified by the model.
+    Falsified(usize),
+}
+
 /// API for SAT validator like [`inject_assignment`](`crate::solver::ValidateIF::inject_assignment`), [`validate`](`crate::solver::ValidateIF::validate`) and so on.
 pub trait ValidateIF {
     /// load a assignment set into solver.
@@ -16,6 +33,16 @@ pub trait ValidateIF {
     fn inject_assignment(&mut self, vec: &[i32]) -> MaybeInconsistent;
     /// return `true` is the loaded assignment set is satisfiable (a model of a problem).
     fn validate(&self) -> Option<Vec<i32>>;
+    #[cfg(not(feature = "no_IO"))]
+    /// check the last model returned by `solve` against the clauses in a CNF file.
+    ///
+    /// # Errors
+    ///
+    /// * `ValidationError::Falsified(n)` if the `n`-th clause (1-based) in the file is
+    ///   falsified by the model.
+    /// * `ValidationError::NoModel` if `solve` hasn't returned a model.
+    /// * `ValidationError::IOError` if the file can't be read or parsed.
+    fn validate_against(&self, path: &Path) -> Result<(), ValidationError>;
 }
 
 impl ValidateIF for Solver {
@@ -68,4 +95,43 @@ impl ValidateIF for Solver {
             .validate(self.asg.assign_ref(), true)
             .map(|cid| Vec::<i32>::from(&self.cdb[cid]))
     }
+    /// returns `Ok(())` if the last model satisfies all clauses in the original CNF file.
+    /// This doesn't depend on the clause database, which has been modified by
+    /// simplification and elimination.
+    ///
+    /// #Example
+    ///
+    /// ```
+    /// use crate::splr::{solver::ValidationError, *};
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("cnfs/sample.cnf");
+    /// let mut s = Solver::try_from(path).expect("can't load");
+    /// assert_eq!(s.validate_against(path), Err(ValidationError::NoModel));
+    /// assert!(matches!(s.solve(), Ok(Certificate::SAT(_))));
+    /// assert_eq!(s.validate_against(path), Ok(()));
+    ///```
+    #[cfg(not(feature = "no_IO"))]
+    fn validate_against(&self, path: &Path) -> Result<(), ValidationError> {
+        let Some(ref model) = self.state.model else {
+            return Err(ValidationError::NoModel);
+        };
+        let satisfies = |l: i32| model.get(l.unsigned_abs() as usize - 1) == Some(&l);
+        let mut index: usize = 0;
+        let mut falsified = false;
+        for_each_clause(path, |lits| {
+            index += 1;
+            falsified = !lits.iter().any(|l| satisfies(*l));
+            if falsified {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .map_err(|_| ValidationError::IOError)?;
+        if falsified {
+            return Err(ValidationError::Falsified(index));
+        }
+        Ok(())
+    }
 }
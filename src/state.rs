@@ -6,13 +6,15 @@ use std::time::{Duration, Instant};
 use {
     crate::{
         assign, cdb,
-        solver::{RestartManager, SolverEvent, StageManager},
+        config::ModelOrder,
+        solver::{RestartManager, SolverEvent, StageManager, TraceEvent},
         types::*,
     },
     std::{
         fmt,
         io::{stdout, Write},
         ops::{Index, IndexMut},
+        sync::{atomic::AtomicBool, Arc},
     },
 };
 const PROGRESS_REPORT_ROWS: usize = 7;
@@ -41,6 +43,17 @@ pub trait StateIF {
     fn log<S: AsRef<str>>(&mut self, tick: Option<(Option<usize>, Option<usize>, usize)>, mes: S);
 }
 
+/// A user-supplied clock returning elapsed time in seconds.
+/// See [`Solver::set_clock`](`crate::solver::Solver::set_clock`).
+#[derive(Clone)]
+pub struct Clock(pub Arc<dyn Fn() -> f64 + Send + Sync>);
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Clock")
+    }
+}
+
 /// stat index.
 #[derive(Clone, Eq, PartialEq)]
 pub enum Stat {
@@ -55,6 +68,12 @@ pub enum Stat {
     Simplify,
     /// the number of subsumed clause by processor
     SubsumedClause,
+    /// the number of duplicate clauses dropped by `Config::dedup_clauses`
+    DuplicateClause,
+    /// the number of learnt clauses not kept by `Config::max_learnt_size`
+    OversizedLearnt,
+    /// the number of phase resets by `Config::stagnation_limit`
+    StagnationReset,
     /// for SLS
     SLS,
     /// don't use this dummy (sentinel at the tail).
@@ -111,16 +130,18 @@ pub struct State {
     pub b_lvl: Ema,
     /// EMA of conflicting levels
     pub c_lvl: Ema,
+    /// EMA of trail sizes at conflicts
+    pub trail_ema: Ema2,
     /// EMA of c_lbd - b_lbd, or Exploration vs. Eploitation
     pub e_mode: Ema2,
     pub e_mode_threshold: f64,
     pub exploration_rate_ema: Ema,
 
-    #[cfg(feature = "support_user_assumption")]
-    /// hold conflicting user-defined *assumed* literals for UNSAT problems
+    /// the assumptions responsible for the last UNSAT by `Solver::solve_under_assumptions`;
+    /// it's empty if the formula itself is unsatisfiable.
     pub conflicts: Vec<Lit>,
 
-    #[cfg(feature = "chronoBT")]
+    #[cfg(feature = "chrono_BT")]
     /// chronoBT threshold
     pub chrono_bt_threshold: DecisionLevel,
 
@@ -130,6 +151,22 @@ pub struct State {
     pub new_learnt: Vec<Lit>,
     /// working place to store given clauses' ids which is used to derive a good learnt
     pub derive20: Vec<ClauseId>,
+    /// the last model returned by `solve`, in var index order
+    pub model: Option<Vec<i32>>,
+    /// the assumption stack used by `Solver::solve_under_assumptions`
+    pub assumptions: Vec<Lit>,
+    /// search events recorded under `Config::record_trace`
+    pub trace: Vec<TraceEvent>,
+    /// set if the empty clause was given; `solve` returns UNSAT immediately then.
+    /// Clauses falsified by root-level assignments are rejected instead.
+    pub inconsistent: bool,
+    /// the given clauses, recorded only under `Config::self_check`
+    pub original_clauses: Vec<Vec<Lit>>,
+    /// vars in the order of their first appearance in the given clauses,
+    /// recorded only under `ModelOrder::InputOrder`
+    pub input_order: Vec<VarId>,
+    /// flags for the vars occurring in given clauses
+    input_seen: Vec<bool>,
     /// `progress` invocation counter
     pub progress_cnt: usize,
     /// keep the previous statistics values
@@ -138,8 +175,13 @@ pub struct State {
     pub sls_index: usize,
     /// start clock for timeout handling
     pub start: Instant,
+    /// clock for timeout handling used instead of `start` if given
+    pub clock: Option<Clock>,
     /// upper limit for timeout handling
     pub time_limit: f64,
+    /// a flag to stop the search from another thread.
+    /// `solve` returns `Certificate::UNKNOWN` after it's set.
+    pub interrupt: Option<Arc<AtomicBool>>,
     /// logging facility.
     log_messages: Vec<String>,
 }
@@ -157,24 +199,33 @@ impl Default for State {
 
             b_lvl: Ema::new(5_000),
             c_lvl: Ema::new(5_000),
+            trail_ema: Ema2::new(100).with_slow(5_000),
             e_mode: Ema2::new(40).with_slow(4_000).with_value(10.0),
             e_mode_threshold: 1.20,
             exploration_rate_ema: Ema::new(1000),
 
-            #[cfg(feature = "support_user_assumption")]
             conflicts: Vec::new(),
 
-            #[cfg(feature = "chronoBT")]
+            #[cfg(feature = "chrono_BT")]
             chrono_bt_threshold: 100,
 
             last_asg: 0,
             new_learnt: Vec::new(),
             derive20: Vec::new(),
+            model: None,
+            assumptions: Vec::new(),
+            trace: Vec::new(),
+            inconsistent: false,
+            original_clauses: Vec::new(),
+            input_order: Vec::new(),
+            input_seen: Vec::new(),
             progress_cnt: 0,
             record: ProgressRecord::default(),
             sls_index: 0,
             start: Instant::now(),
+            clock: None,
             time_limit: 0.0,
+            interrupt: None,
             log_messages: Vec::new(),
         }
     }
@@ -220,13 +271,17 @@ impl Instantiate for State {
     fn handle(&mut self, e: SolverEvent) {
         match e {
             SolverEvent::NewVar => {
+                // `Eliminator` is sized by `cnf`
+                self.cnf.num_of_variables += 1;
                 self.target.num_of_variables += 1;
             }
             SolverEvent::Assert(_) => (),
             SolverEvent::Conflict => (),
             SolverEvent::Eliminate(_) => (),
             SolverEvent::Instantiate => (),
-            SolverEvent::Reinitialize => (),
+            SolverEvent::Reinitialize => {
+                self.model = None;
+            }
             SolverEvent::Restart => {
                 self[Stat::Restart] += 1;
                 self.restart.handle(SolverEvent::Restart);
@@ -370,13 +425,10 @@ macro_rules! f {
 
 impl StateIF for State {
     fn is_timeout(&self) -> bool {
-        Duration::from_secs(self.config.c_timeout as u64) < self.start.elapsed()
+        Duration::from_secs(self.config.c_timeout as u64).as_secs_f64() < self.elapsed_secs()
     }
     fn elapsed(&self) -> Option<f64> {
-        Some(
-            self.start.elapsed().as_secs_f64()
-                / Duration::from_secs(self.config.c_timeout as u64).as_secs_f64(),
-        )
+        Some(self.elapsed_secs() / Duration::from_secs(self.config.c_timeout as u64).as_secs_f64())
     }
     fn progress_header(&mut self) {
         if !self.config.splr_interface || self.config.quiet_mode {
@@ -615,6 +667,67 @@ impl StateIF for State {
 }
 
 impl State {
+    /// return elapsed time in seconds, measured by `clock` if given.
+    pub fn elapsed_secs(&self) -> f64 {
+        self.clock
+            .as_ref()
+            .map_or_else(|| self.start.elapsed().as_secs_f64(), |c| (c.0)())
+    }
+    /// record the vars in a given clause, and their order for `ModelOrder::InputOrder`.
+    pub fn record_input_order(&mut self, lits: &[Lit]) {
+        let in_order = self.config.model_order == ModelOrder::InputOrder;
+        for l in lits.iter() {
+            let vi = l.vi();
+            if self.input_seen.len() <= vi {
+                self.input_seen.resize(vi + 1, false);
+            }
+            if !self.input_seen[vi] {
+                self.input_seen[vi] = true;
+                if in_order {
+                    self.input_order.push(vi);
+                }
+            }
+        }
+    }
+    /// return `true` if the var occurs in a given clause.
+    pub fn is_input_var(&self, vi: VarId) -> bool {
+        self.input_seen.get(vi).map_or(false, |b| *b)
+    }
+    /// record a given clause for `Config::self_check`.
+    pub fn record_original_clause(&mut self, lits: &[Lit]) {
+        if self.config.self_check {
+            self.original_clauses.push(lits.to_vec());
+        }
+    }
+    /// return the first recorded clause falsified by `model`, under `Config::self_check`.
+    pub fn check_original_clauses(&self, model: &[Option<bool>]) -> Option<&[Lit]> {
+        self.original_clauses
+            .iter()
+            .find(|c| {
+                !c.iter()
+                    .any(|l| model.get(l.vi()).copied().flatten() == Some(bool::from(*l)))
+            })
+            .map(|c| c.as_slice())
+    }
+    /// reorder a model in var index order according to `Config::model_order`.
+    pub fn order_model(&self, model: Vec<i32>) -> Vec<i32> {
+        if self.config.model_order != ModelOrder::InputOrder {
+            return model;
+        }
+        let mut vals = self
+            .input_order
+            .iter()
+            .filter(|vi| **vi <= model.len())
+            .map(|vi| model[vi - 1])
+            .collect::<Vec<i32>>();
+        vals.extend(model.iter().filter(|i| {
+            !self
+                .input_seen
+                .get(i.unsigned_abs() as usize)
+                .map_or(false, |b| *b)
+        }));
+        vals
+    }
     #[allow(clippy::cognitive_complexity)]
     fn record_stats<A, C>(&mut self, asg: &A, cdb: &C)
     where
@@ -676,7 +789,7 @@ impl State {
 
 impl fmt::Display for State {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let tm: f64 = (self.start.elapsed().as_millis() as f64) / 1_000.0;
+        let tm: f64 = self.elapsed_secs();
         let vc = format!(
             "{},{}",
             self.target.num_of_variables, self.target.num_of_clauses,
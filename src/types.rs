@@ -11,10 +11,14 @@ pub use crate::{
 use std::{
     cmp::Ordering,
     fmt,
-    fs::File,
-    io::{BufRead, BufReader},
     num::NonZeroU32,
     ops::{Index, IndexMut, Not},
+};
+
+#[cfg(not(feature = "no_IO"))]
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
     path::Path,
 };
 
@@ -45,25 +49,13 @@ pub trait ActivityIF<Ix> {
     /// set activity
     fn set_activity(&mut self, ix: Ix, val: f64);
     /// modify one's activity at conflict analysis in `conflict_analyze` in [`solver`](`crate::solver`).
-    fn reward_at_analysis(&mut self, _ix: Ix) {
-        #[cfg(debug)]
-        todo!()
-    }
+    fn reward_at_analysis(&mut self, _ix: Ix) {}
     /// modify one's activity at value assignment in assign.
-    fn reward_at_assign(&mut self, _ix: Ix) {
-        #[cfg(debug)]
-        todo!()
-    }
+    fn reward_at_assign(&mut self, _ix: Ix) {}
     /// modify one's activity at value assignment in unit propagation.
-    fn reward_at_propagation(&mut self, _ix: Ix) {
-        #[cfg(debug)]
-        todo!()
-    }
+    fn reward_at_propagation(&mut self, _ix: Ix) {}
     /// modify one's activity at value un-assignment in [`cancel_until`](`crate::assign::PropagateIF::cancel_until`).
-    fn reward_at_unassign(&mut self, _ix: Ix) {
-        #[cfg(debug)]
-        todo!()
-    }
+    fn reward_at_unassign(&mut self, _ix: Ix) {}
     /// update reward decay.
     fn update_activity_decay(&mut self, _decay: f64);
     /// update internal counter.
@@ -154,6 +146,25 @@ impl From<(VarId, bool)> for Lit {
     }
 }
 
+impl Lit {
+    /// return the positive literal of var `vi` if `positive`, otherwise the negative one.
+    /// `vi` should be in the range of `1..=i32::MAX` to be converted to a DIMACS int.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use splr::types::*;
+    /// assert_eq!(Lit::new(3, true), Lit::from(3i32));
+    /// assert_eq!(Lit::new(3, false), Lit::from(-3i32));
+    /// assert_eq!(Lit::new(3, false).vi(), 3);
+    /// ```
+    #[inline]
+    pub fn new(vi: VarId, positive: bool) -> Lit {
+        debug_assert!(0 < vi && vi <= i32::MAX as VarId, "invalid var id {vi}");
+        Lit::from((vi, positive))
+    }
+}
+
 impl From<usize> for Lit {
     #[inline]
     fn from(l: usize) -> Self {
@@ -172,12 +183,18 @@ impl From<u32> for Lit {
     }
 }
 
+/// The inverse of `i32::from(Lit)` for any non-zero int except `i32::MIN`.
 impl From<i32> for Lit {
     #[inline]
     fn from(x: i32) -> Self {
+        debug_assert!(x != 0 && x != i32::MIN, "invalid literal {x}");
         Lit {
             ordinal: unsafe {
-                NonZeroU32::new_unchecked((if x < 0 { -2 * x } else { 2 * x + 1 }) as u32)
+                NonZeroU32::new_unchecked(if x < 0 {
+                    x.unsigned_abs() << 1
+                } else {
+                    ((x as u32) << 1) + 1
+                })
             },
         }
     }
@@ -377,6 +394,10 @@ pub enum SolverError {
     InvalidLiteral,
     // Exceptions caused by file operations
     IOError,
+    // A clause handle refers to a clause which was removed already
+    InvalidClauseRef,
+    // A CNF file has no valid header, or a clause before its header
+    ParsingCNF(String),
     // UNSAT with some internal context
     Inconsistent,
     OutOfMemory,
@@ -462,7 +483,15 @@ impl<V: AsRef<[i32]>> From<&[V]> for CNFDescription {
     fn from(vec: &[V]) -> Self {
         let num_of_variables = vec
             .iter()
-            .map(|clause| clause.as_ref().iter().map(|l| l.abs()).max().unwrap_or(0))
+            // `i32::MIN` is rejected later as an invalid literal.
+            .map(|clause| {
+                clause
+                    .as_ref()
+                    .iter()
+                    .map(|l| l.checked_abs().unwrap_or(0))
+                    .max()
+                    .unwrap_or(0)
+            })
             .max()
             .unwrap_or(0) as usize;
         CNFDescription {
@@ -477,12 +506,14 @@ impl<V: AsRef<[i32]>> From<&[V]> for CNFDescription {
 /// To make CNFDescription clone-able, a BufReader should be separated from it.
 /// If you want to make a CNFDescription which isn't connected to a file,
 /// just call CNFDescription::default() directly.
+#[cfg(not(feature = "no_IO"))]
 #[derive(Debug)]
 pub struct CNFReader {
     pub cnf: CNFDescription,
     pub reader: BufReader<File>,
 }
 
+#[cfg(not(feature = "no_IO"))]
 impl TryFrom<&Path> for CNFReader {
     type Error = SolverError;
     fn try_from(path: &Path) -> Result<Self, Self::Error> {
@@ -497,37 +528,7 @@ impl TryFrom<&Path> for CNFReader {
         };
         let fs = File::open(path).map_or(Err(SolverError::IOError), Ok)?;
         let mut reader = BufReader::new(fs);
-        let mut buf = String::new();
-        let mut nv: usize = 0;
-        let mut nc: usize = 0;
-        let mut found_valid_header = false;
-        loop {
-            buf.clear();
-            match reader.read_line(&mut buf) {
-                Ok(0) => break,
-                Ok(_k) => {
-                    let mut iter = buf.split_whitespace();
-                    if iter.next() == Some("p") && iter.next() == Some("cnf") {
-                        if let Some(v) = iter.next().map(|s| s.parse::<usize>().ok().unwrap()) {
-                            if let Some(c) = iter.next().map(|s| s.parse::<usize>().ok().unwrap()) {
-                                nv = v;
-                                nc = c;
-                                found_valid_header = true;
-                                break;
-                            }
-                        }
-                    }
-                    continue;
-                }
-                Err(e) => {
-                    println!("{e}");
-                    return Err(SolverError::IOError);
-                }
-            }
-        }
-        if !found_valid_header {
-            return Err(SolverError::IOError);
-        }
+        let (nv, nc) = read_cnf_header(&mut reader, &pathname)?;
         let cnf = CNFDescription {
             num_of_variables: nv,
             num_of_clauses: nc,
@@ -537,6 +538,65 @@ impl TryFrom<&Path> for CNFReader {
     }
 }
 
+/// read lines until the DIMACS CNF header, and return the number of vars and clauses.
+/// `pathname` is used in error messages.
+#[cfg(not(feature = "no_IO"))]
+pub(crate) fn read_cnf_header(
+    reader: &mut impl BufRead,
+    pathname: &str,
+) -> Result<(usize, usize), SolverError> {
+    let mut buf = String::new();
+    let mut nv: usize = 0;
+    let mut nc: usize = 0;
+    let mut found_valid_header = false;
+    loop {
+        buf.clear();
+        match reader.read_line(&mut buf) {
+            Ok(0) => break,
+            Ok(_) if buf.trim_start().starts_with('c') => continue,
+            Ok(_k) => {
+                let mut iter = buf.split_whitespace();
+                match iter.next() {
+                    None => continue,
+                    Some("p") if iter.next() == Some("cnf") => {
+                        let mut num = || iter.next().and_then(|s| s.parse::<usize>().ok());
+                        if let (Some(v), Some(c)) = (num(), num()) {
+                            nv = v;
+                            nc = c;
+                            found_valid_header = true;
+                            break;
+                        }
+                        return Err(SolverError::ParsingCNF(format!(
+                            "{pathname}: invalid header: {}",
+                            buf.trim()
+                        )));
+                    }
+                    Some("p") => {
+                        return Err(SolverError::ParsingCNF(format!(
+                            "{pathname}: not a CNF header: {}",
+                            buf.trim()
+                        )));
+                    }
+                    Some(_) => {
+                        return Err(SolverError::ParsingCNF(format!(
+                            "{pathname}: a clause appears before the header: {}",
+                            buf.trim()
+                        )));
+                    }
+                }
+            }
+            Err(e) => {
+                println!("{e}");
+                return Err(SolverError::IOError);
+            }
+        }
+    }
+    if !found_valid_header {
+        return Err(SolverError::ParsingCNF(format!("{pathname}: no header")));
+    }
+    Ok((nv, nc))
+}
+
 impl<T> Delete<T> for Vec<T> {
     fn delete_unstable<F>(&mut self, filter: F)
     where
@@ -577,6 +637,12 @@ bitflags! {
         const OCCUR_LINKED = 0b0000_1000;
         /// a given clause derived a learnt which LBD is smaller than 20.
         const DERIVE20     = 0b0001_0000;
+        /// a learnt clause whose LBD dropped at the last analysis; it survives the next reduction.
+        const PROTECTED    = 0b0010_0000;
+        /// a binary clause derived from learning, which isn't removable but isn't given.
+        const BI_LEARNT    = 0b0100_0000;
+        /// a learnt clause longer than `Config::max_learnt_size`; it's removed at the next reduction.
+        const OVERSIZED    = 0b1000_0000;
     }
 }
 
@@ -594,6 +660,8 @@ bitflags! {
         const ENQUEUED     = 0b0000_1000;
         /// a var is checked during in the current conflict analysis.
         const CA_SEEN      = 0b0001_0000;
+        /// a var is protected from elimination.
+        const FROZEN       = 0b0100_0000;
 
         #[cfg(feature = "debug_propagation")]
         /// check propagation
@@ -601,17 +669,20 @@ bitflags! {
     }
 }
 
+#[cfg(not(feature = "no_IO"))]
 #[derive(Debug, Default)]
 pub struct Logger {
     dest: Option<File>,
 }
 
+#[cfg(not(feature = "no_IO"))]
 impl fmt::Display for Logger {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Dump({:?})", self.dest)
     }
 }
 
+#[cfg(not(feature = "no_IO"))]
 impl Logger {
     pub fn new<T: AsRef<str>>(fname: T) -> Self {
         Logger {
@@ -629,6 +700,9 @@ impl Logger {
     }
 }
 
+/// A pair of a body and its f64 index, ordered by the index.
+/// Ties are broken by the body in reverse order, so that a `BinaryHeap` pops
+/// the smallest body first among the elements with the same index.
 #[derive(Clone, Debug)]
 pub struct OrderedProxy<T: Clone + Default + Sized + Ord> {
     index: f64,
@@ -646,7 +720,7 @@ impl<T: Clone + Default + Sized + Ord> Default for OrderedProxy<T> {
 
 impl<T: Clone + Default + Sized + Ord> PartialEq for OrderedProxy<T> {
     fn eq(&self, other: &OrderedProxy<T>) -> bool {
-        self.index == other.index && self.body == other.body
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -660,13 +734,9 @@ impl<T: Clone + Default + PartialEq + Ord> PartialOrd for OrderedProxy<T> {
 
 impl<T: Clone + Default + PartialEq + Ord> Ord for OrderedProxy<T> {
     fn cmp(&self, other: &OrderedProxy<T>) -> Ordering {
-        if (self.index - other.index).abs() < f64::EPSILON {
-            self.body.cmp(&other.body)
-        } else if self.index < other.index {
-            Ordering::Less
-        } else {
-            Ordering::Greater
-        }
+        self.index
+            .total_cmp(&other.index)
+            .then_with(|| other.body.cmp(&self.body))
     }
 }
 
@@ -715,8 +785,8 @@ pub enum VarState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::Path;
 
+    #[cfg(not(feature = "no_IO"))]
     #[test]
     fn test_cnf() {
         if let Ok(reader) = CNFReader::try_from(Path::new("cnfs/sample.cnf")) {
@@ -726,4 +796,73 @@ mod tests {
             panic!("failed to load cnfs/sample.cnf");
         }
     }
+    #[cfg(not(feature = "no_IO"))]
+    #[test]
+    fn test_cnf_header() {
+        let check = |name: &str, body: &str| {
+            let path = std::env::temp_dir().join(name);
+            std::fs::write(&path, body).expect("can't write");
+            let res = CNFReader::try_from(path.as_path()).map(|r| r.cnf.num_of_clauses);
+            std::fs::remove_file(&path).expect("can't remove");
+            res
+        };
+        assert_eq!(
+            check("splr-header1.cnf", "c a cnf\np  cnf   3  2\n1 2 0\n-3 0\n"),
+            Ok(2)
+        );
+        assert_eq!(
+            check(
+                "splr-header2.cnf",
+                "c p cnf 9 9\n  c indented\n\np cnf 3 1\n1 0\n"
+            ),
+            Ok(1)
+        );
+        assert!(matches!(
+            check("splr-header3.cnf", "p dnf 3 2\n1 2 0\n"),
+            Err(SolverError::ParsingCNF(_))
+        ));
+        assert!(matches!(
+            check("splr-header4.cnf", "p cnf 3\n1 2 0\n"),
+            Err(SolverError::ParsingCNF(_))
+        ));
+        assert!(matches!(
+            check("splr-header5.cnf", "1 2 0\np cnf 3 1\n"),
+            Err(SolverError::ParsingCNF(m)) if m.contains("before the header")
+        ));
+        assert!(matches!(
+            check("splr-header6.cnf", "c no header\n"),
+            Err(SolverError::ParsingCNF(_))
+        ));
+    }
+    #[test]
+    fn test_lit_conversion() {
+        let max = i32::MAX;
+        for i in (1..=100_000).chain(max - 100_000..=max) {
+            for x in [i, -i] {
+                let l = Lit::from(x);
+                assert_eq!(i32::from(l), x);
+                assert_eq!(l, Lit::new(i as VarId, 0 < x));
+                assert_eq!(l.vi(), i as VarId);
+                assert_eq!(!l, Lit::from(-x));
+            }
+        }
+    }
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic]
+    fn test_lit_from_zero() {
+        let _ = Lit::from(0i32);
+    }
+    #[test]
+    fn test_ordered_proxy() {
+        use std::collections::BinaryHeap;
+        let mut heap = BinaryHeap::new();
+        for vi in [4, 2, 5, 1, 3] {
+            heap.push(OrderedProxy::new(vi, 1.0));
+        }
+        heap.push(OrderedProxy::new(6, 0.5));
+        heap.push(OrderedProxy::new(7, 2.0));
+        let order = std::iter::from_fn(|| heap.pop().map(|p| p.to())).collect::<Vec<usize>>();
+        assert_eq!(order, vec![7, 1, 2, 3, 4, 5, 6]);
+    }
 }
@@ -94,6 +94,10 @@ fn run(cnf: &Path, assigns: &[i32], switch: bool) -> usize {
                 println!("s UNSATISFIABLE");
                 break;
             }
+            Ok(Certificate::UNKNOWN) => {
+                println!("s UNKNOWN");
+                break;
+            }
             Err(e) => {
                 println!("s UNKNOWN; {}", e);
                 break;